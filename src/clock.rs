@@ -0,0 +1,111 @@
+use crate::timestamp::{ParseTimestampError, Timestamp};
+use std::cell::Cell;
+use time::{Duration, OffsetDateTime};
+
+/// Source of the current instant, so conversions that stamp missing `created`
+/// values stay deterministic in tests and replay jobs.
+pub trait Clock {
+    fn now(&self) -> Timestamp;
+
+    /// Like [`Clock::now`], but reports a reading that is no valid [`Timestamp`]
+    /// instead of panicking. Clocks reading a raw instant override it.
+    fn try_now(&self) -> Result<Timestamp, ParseTimestampError> {
+        Ok(self.now())
+    }
+}
+
+/// The system's wall clock.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+
+    fn try_now(&self) -> Result<Timestamp, ParseTimestampError> {
+        Timestamp::try_from(OffsetDateTime::now_utc())
+    }
+}
+
+/// Always returns the same instant.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct FixedClock(pub Timestamp);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+/// Returns an instant that only changes when set or advanced explicitly.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Cell<Timestamp>,
+}
+
+impl ManualClock {
+    pub fn new(start: Timestamp) -> Self {
+        ManualClock {
+            now: Cell::new(start),
+        }
+    }
+
+    pub fn set(&self, now: Timestamp) {
+        self.now.set(now);
+    }
+
+    /// Moves the clock by `duration`, which may be negative.
+    ///
+    /// Panics if the result is no valid [`Timestamp`].
+    pub fn advance(&self, duration: Duration) {
+        let advanced = self.now.get().to_offset_date_time() + duration;
+        self.now
+            .set(Timestamp::try_from(advanced).expect("manual clock advanced out of range"));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::{Clock, FixedClock, ManualClock, SystemClock};
+    use crate::timestamp::Timestamp;
+    use time::Duration;
+
+    fn timestamp(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn should_return_fixed_instant() {
+        let clock = FixedClock(timestamp("2010-01-01T12:00:00.001Z"));
+
+        assert_eq!(clock.now(), clock.now());
+        assert_eq!(clock.now(), timestamp("2010-01-01T12:00:00.001Z"));
+    }
+
+    #[test]
+    fn should_set_and_advance_manual_clock() {
+        let clock = ManualClock::new(timestamp("2010-01-01T12:00:00.000Z"));
+
+        clock.advance(Duration::milliseconds(1500));
+        assert_eq!(clock.now(), timestamp("2010-01-01T12:00:01.500Z"));
+
+        clock.set(timestamp("2010-02-01T00:00:00.000Z"));
+        assert_eq!(clock.now(), timestamp("2010-02-01T00:00:00.000Z"));
+    }
+
+    #[test]
+    fn should_not_go_backwards_on_system_clock() {
+        let clock = SystemClock;
+
+        let first = clock.now();
+
+        assert!(clock.now() >= first);
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+macro_rules! iso_4217 {
+    ($($code:ident => ($numeric:expr, $digits:expr, $symbol:expr, $name:expr)),* $(,)?) => {
+        // ISO 4217
+        #[derive(
+            Serialize, Deserialize, Copy, Clone, Display, EnumString, EnumIter, Eq, PartialEq, Debug, Hash,
+        )]
+        pub enum Currency {
+            $($code,)*
+        }
+
+        impl Currency {
+            /// ISO 4217 numeric code, e.g. `978` for EUR.
+            pub fn numeric(&self) -> u16 {
+                match self {
+                    $(Currency::$code => $numeric,)*
+                }
+            }
+
+            /// Number of decimal digits of the minor unit, e.g. `2` for cents.
+            pub fn minor_unit_exponent(&self) -> u8 {
+                match self {
+                    $(Currency::$code => $digits,)*
+                }
+            }
+
+            /// Local symbol, e.g. `€`. Falls back to the alpha code where none is common.
+            pub fn symbol(&self) -> &'static str {
+                match self {
+                    $(Currency::$code => $symbol,)*
+                }
+            }
+
+            /// English ISO 4217 currency name.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Currency::$code => $name,)*
+                }
+            }
+        }
+    };
+}
+
+// Active currencies that define a minor unit. Units without one (precious metals,
+// XDR, bond-market and testing codes) are omitted, since they cannot be rounded.
+iso_4217! {
+    AED => (784, 2, "د.إ", "UAE Dirham"),
+    AFN => (971, 2, "؋", "Afghani"),
+    ALL => (8, 2, "L", "Lek"),
+    AMD => (51, 2, "֏", "Armenian Dram"),
+    ANG => (532, 2, "ƒ", "Netherlands Antillean Guilder"),
+    AOA => (973, 2, "Kz", "Kwanza"),
+    ARS => (32, 2, "$", "Argentine Peso"),
+    AUD => (36, 2, "A$", "Australian Dollar"),
+    AWG => (533, 2, "Afl.", "Aruban Florin"),
+    AZN => (944, 2, "₼", "Azerbaijan Manat"),
+    BAM => (977, 2, "KM", "Convertible Mark"),
+    BBD => (52, 2, "Bds$", "Barbados Dollar"),
+    BDT => (50, 2, "৳", "Taka"),
+    BGN => (975, 2, "лв", "Bulgarian Lev"),
+    BHD => (48, 3, ".د.ب", "Bahraini Dinar"),
+    BIF => (108, 0, "FBu", "Burundi Franc"),
+    BMD => (60, 2, "BD$", "Bermudian Dollar"),
+    BND => (96, 2, "B$", "Brunei Dollar"),
+    BOB => (68, 2, "Bs.", "Boliviano"),
+    BOV => (984, 2, "BOV", "Mvdol"),
+    BRL => (986, 2, "R$", "Brazilian Real"),
+    BSD => (44, 2, "B$", "Bahamian Dollar"),
+    BTN => (64, 2, "Nu.", "Ngultrum"),
+    BWP => (72, 2, "P", "Pula"),
+    BYN => (933, 2, "Br", "Belarusian Ruble"),
+    BZD => (84, 2, "BZ$", "Belize Dollar"),
+    CAD => (124, 2, "CA$", "Canadian Dollar"),
+    CDF => (976, 2, "FC", "Congolese Franc"),
+    CHE => (947, 2, "CHE", "WIR Euro"),
+    CHF => (756, 2, "CHF", "Swiss Franc"),
+    CHW => (948, 2, "CHW", "WIR Franc"),
+    CLF => (990, 4, "UF", "Unidad de Fomento"),
+    CLP => (152, 0, "$", "Chilean Peso"),
+    CNY => (156, 2, "¥", "Yuan Renminbi"),
+    COP => (170, 2, "$", "Colombian Peso"),
+    COU => (970, 2, "COU", "Unidad de Valor Real"),
+    CRC => (188, 2, "₡", "Costa Rican Colon"),
+    CUP => (192, 2, "$MN", "Cuban Peso"),
+    CVE => (132, 2, "Esc", "Cabo Verde Escudo"),
+    CZK => (203, 2, "Kč", "Czech Koruna"),
+    DJF => (262, 0, "Fdj", "Djibouti Franc"),
+    DKK => (208, 2, "kr.", "Danish Krone"),
+    DOP => (214, 2, "RD$", "Dominican Peso"),
+    DZD => (12, 2, "د.ج", "Algerian Dinar"),
+    EGP => (818, 2, "E£", "Egyptian Pound"),
+    ERN => (232, 2, "Nfk", "Nakfa"),
+    ETB => (230, 2, "Br", "Ethiopian Birr"),
+    EUR => (978, 2, "€", "Euro"),
+    FJD => (242, 2, "FJ$", "Fiji Dollar"),
+    FKP => (238, 2, "£", "Falkland Islands Pound"),
+    GBP => (826, 2, "£", "Pound Sterling"),
+    GEL => (981, 2, "₾", "Lari"),
+    GHS => (936, 2, "GH₵", "Ghana Cedi"),
+    GIP => (292, 2, "£", "Gibraltar Pound"),
+    GMD => (270, 2, "D", "Dalasi"),
+    GNF => (324, 0, "FG", "Guinean Franc"),
+    GTQ => (320, 2, "Q", "Quetzal"),
+    GYD => (328, 2, "G$", "Guyana Dollar"),
+    HKD => (344, 2, "HK$", "Hong Kong Dollar"),
+    HNL => (340, 2, "L", "Lempira"),
+    HTG => (332, 2, "G", "Gourde"),
+    HUF => (348, 2, "Ft", "Forint"),
+    IDR => (360, 2, "Rp", "Rupiah"),
+    ILS => (376, 2, "₪", "New Israeli Sheqel"),
+    INR => (356, 2, "₹", "Indian Rupee"),
+    IQD => (368, 3, "ع.د", "Iraqi Dinar"),
+    IRR => (364, 2, "﷼", "Iranian Rial"),
+    ISK => (352, 0, "kr", "Iceland Krona"),
+    JMD => (388, 2, "J$", "Jamaican Dollar"),
+    JOD => (400, 3, "د.ا", "Jordanian Dinar"),
+    JPY => (392, 0, "¥", "Yen"),
+    KES => (404, 2, "KSh", "Kenyan Shilling"),
+    KGS => (417, 2, "сом", "Som"),
+    KHR => (116, 2, "៛", "Riel"),
+    KMF => (174, 0, "CF", "Comorian Franc"),
+    KPW => (408, 2, "₩", "North Korean Won"),
+    KRW => (410, 0, "₩", "Won"),
+    KWD => (414, 3, "د.ك", "Kuwaiti Dinar"),
+    KYD => (136, 2, "CI$", "Cayman Islands Dollar"),
+    KZT => (398, 2, "₸", "Tenge"),
+    LAK => (418, 2, "₭", "Lao Kip"),
+    LBP => (422, 2, "ل.ل", "Lebanese Pound"),
+    LKR => (144, 2, "Rs", "Sri Lanka Rupee"),
+    LRD => (430, 2, "L$", "Liberian Dollar"),
+    LSL => (426, 2, "L", "Loti"),
+    LYD => (434, 3, "ل.د", "Libyan Dinar"),
+    MAD => (504, 2, "د.م.", "Moroccan Dirham"),
+    MDL => (498, 2, "L", "Moldovan Leu"),
+    MGA => (969, 2, "Ar", "Malagasy Ariary"),
+    MKD => (807, 2, "ден", "Denar"),
+    MMK => (104, 2, "K", "Kyat"),
+    MNT => (496, 2, "₮", "Tugrik"),
+    MOP => (446, 2, "MOP$", "Pataca"),
+    MRU => (929, 2, "UM", "Ouguiya"),
+    MUR => (480, 2, "₨", "Mauritius Rupee"),
+    MVR => (462, 2, "Rf", "Rufiyaa"),
+    MWK => (454, 2, "MK", "Malawi Kwacha"),
+    MXN => (484, 2, "MX$", "Mexican Peso"),
+    MXV => (979, 2, "MXV", "Mexican Unidad de Inversion (UDI)"),
+    MYR => (458, 2, "RM", "Malaysian Ringgit"),
+    MZN => (943, 2, "MT", "Mozambique Metical"),
+    NAD => (516, 2, "N$", "Namibia Dollar"),
+    NGN => (566, 2, "₦", "Naira"),
+    NIO => (558, 2, "C$", "Cordoba Oro"),
+    NOK => (578, 2, "kr", "Norwegian Krone"),
+    NPR => (524, 2, "Rs", "Nepalese Rupee"),
+    NZD => (554, 2, "NZ$", "New Zealand Dollar"),
+    OMR => (512, 3, "ر.ع.", "Rial Omani"),
+    PAB => (590, 2, "B/.", "Balboa"),
+    PEN => (604, 2, "S/", "Sol"),
+    PGK => (598, 2, "K", "Kina"),
+    PHP => (608, 2, "₱", "Philippine Peso"),
+    PKR => (586, 2, "Rs", "Pakistan Rupee"),
+    PLN => (985, 2, "zł", "Zloty"),
+    PYG => (600, 0, "₲", "Guarani"),
+    QAR => (634, 2, "ر.ق", "Qatari Rial"),
+    RON => (946, 2, "lei", "Romanian Leu"),
+    RSD => (941, 2, "дин.", "Serbian Dinar"),
+    RUB => (643, 2, "₽", "Russian Ruble"),
+    RWF => (646, 0, "FRw", "Rwanda Franc"),
+    SAR => (682, 2, "ر.س", "Saudi Riyal"),
+    SBD => (90, 2, "SI$", "Solomon Islands Dollar"),
+    SCR => (690, 2, "SR", "Seychelles Rupee"),
+    SDG => (938, 2, "ج.س.", "Sudanese Pound"),
+    SEK => (752, 2, "kr", "Swedish Krona"),
+    SGD => (702, 2, "S$", "Singapore Dollar"),
+    SHP => (654, 2, "£", "Saint Helena Pound"),
+    SLE => (925, 2, "Le", "Leone"),
+    SOS => (706, 2, "Sh.So.", "Somali Shilling"),
+    SRD => (968, 2, "Sr$", "Surinam Dollar"),
+    SSP => (728, 2, "SS£", "South Sudanese Pound"),
+    STN => (930, 2, "Db", "Dobra"),
+    SVC => (222, 2, "₡", "El Salvador Colon"),
+    SYP => (760, 2, "£S", "Syrian Pound"),
+    SZL => (748, 2, "E", "Lilangeni"),
+    THB => (764, 2, "฿", "Baht"),
+    TJS => (972, 2, "SM", "Somoni"),
+    TMT => (934, 2, "m", "Turkmenistan New Manat"),
+    TND => (788, 3, "د.ت", "Tunisian Dinar"),
+    TOP => (776, 2, "T$", "Pa'anga"),
+    TRY => (949, 2, "₺", "Turkish Lira"),
+    TTD => (780, 2, "TT$", "Trinidad and Tobago Dollar"),
+    TWD => (901, 2, "NT$", "New Taiwan Dollar"),
+    TZS => (834, 2, "TSh", "Tanzanian Shilling"),
+    UAH => (980, 2, "₴", "Hryvnia"),
+    UGX => (800, 0, "USh", "Uganda Shilling"),
+    USD => (840, 2, "$", "US Dollar"),
+    USN => (997, 2, "USN", "US Dollar (Next day)"),
+    UYI => (940, 0, "UYI", "Uruguay Peso en Unidades Indexadas (UI)"),
+    UYU => (858, 2, "$U", "Peso Uruguayo"),
+    UYW => (927, 4, "UYW", "Unidad Previsional"),
+    UZS => (860, 2, "soʻm", "Uzbekistan Sum"),
+    VED => (926, 2, "Bs.D", "Bolívar Soberano"),
+    VES => (928, 2, "Bs.S", "Bolívar Soberano"),
+    VND => (704, 0, "₫", "Dong"),
+    VUV => (548, 0, "VT", "Vatu"),
+    WST => (882, 2, "WS$", "Tala"),
+    XAF => (950, 0, "FCFA", "CFA Franc BEAC"),
+    XCD => (951, 2, "EC$", "East Caribbean Dollar"),
+    XOF => (952, 0, "CFA", "CFA Franc BCEAO"),
+    XPF => (953, 0, "CFP", "CFP Franc"),
+    YER => (886, 2, "YR", "Yemeni Rial"),
+    ZAR => (710, 2, "R", "Rand"),
+    ZMW => (967, 2, "ZK", "Zambian Kwacha"),
+    ZWG => (924, 2, "ZiG", "Zimbabwe Gold"),
+}
+
+/// Currency conventionally meant by a symbol that several currencies share.
+const PREFERRED_BY_SYMBOL: &[(&str, Currency)] = &[
+    ("$", Currency::USD),
+    ("£", Currency::GBP),
+    ("¥", Currency::JPY),
+    ("₩", Currency::KRW),
+    ("₡", Currency::CRC),
+];
+
+impl Currency {
+    pub fn from_numeric(numeric: u16) -> Option<Currency> {
+        Currency::iter().find(|currency| currency.numeric() == numeric)
+    }
+
+    /// Every currency using `symbol`, e.g. `kr` yields ISK, NOK and SEK.
+    pub fn all_by_symbol(symbol: &str) -> impl Iterator<Item = Currency> + '_ {
+        Currency::iter().filter(move |currency| currency.symbol() == symbol)
+    }
+
+    /// Currency for `symbol` if it is unique, or the one conventionally meant by a shared
+    /// symbol (`$` is USD, `£` is GBP, `¥` is JPY). `None` for ambiguous symbols like `kr`.
+    pub fn from_symbol(symbol: &str) -> Option<Currency> {
+        if let Some((_, currency)) = PREFERRED_BY_SYMBOL.iter().find(|(s, _)| *s == symbol) {
+            return Some(*currency);
+        }
+        let mut matches = Currency::all_by_symbol(symbol);
+        match (matches.next(), matches.next()) {
+            (Some(currency), None) => Some(currency),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::currency::Currency;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    #[rstest]
+    #[case(Currency::EUR, 978, 2)]
+    #[case(Currency::CHF, 756, 2)]
+    #[case(Currency::JPY, 392, 0)]
+    #[case(Currency::KWD, 414, 3)]
+    #[case(Currency::CLF, 990, 4)]
+    #[case(Currency::ALL, 8, 2)]
+    fn should_know_numeric_code_and_minor_unit(
+        #[case] currency: Currency,
+        #[case] numeric: u16,
+        #[case] digits: u8,
+    ) {
+        assert_eq!(currency.numeric(), numeric);
+        assert_eq!(currency.minor_unit_exponent(), digits);
+        assert_eq!(Currency::from_numeric(numeric), Some(currency));
+    }
+
+    #[test]
+    fn should_have_unique_numeric_codes() {
+        let numerics: HashSet<u16> = Currency::iter().map(|c| c.numeric()).collect();
+        assert_eq!(numerics.len(), Currency::iter().count());
+    }
+
+    #[rstest]
+    #[case("€", Some(Currency::EUR))]
+    #[case("$", Some(Currency::USD))]
+    #[case("£", Some(Currency::GBP))]
+    #[case("zł", Some(Currency::PLN))]
+    #[case("Kč", Some(Currency::CZK))]
+    #[case("kr", None)]
+    #[case("?", None)]
+    fn should_look_up_currency_by_symbol(#[case] symbol: &str, #[case] expected: Option<Currency>) {
+        assert_eq!(Currency::from_symbol(symbol), expected);
+    }
+
+    #[test]
+    fn should_list_all_currencies_sharing_a_symbol() {
+        let actual: HashSet<Currency> = Currency::all_by_symbol("kr").collect();
+        let expected = HashSet::from([Currency::ISK, Currency::NOK, Currency::SEK]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(Currency::CHF, "\"CHF\"")]
+    #[case(Currency::SEK, "\"SEK\"")]
+    #[case(Currency::JPY, "\"JPY\"")]
+    fn should_keep_alpha_codes_for_display_from_str_and_serde(
+        #[case] currency: Currency,
+        #[case] json: &str,
+    ) {
+        let code = currency.to_string();
+        assert_eq!(serde_json::to_string(&currency).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Currency>(json).unwrap(), currency);
+        assert_eq!(Currency::from_str(&code).unwrap(), currency);
+    }
+
+    #[test]
+    fn should_name_currency() {
+        assert_eq!(Currency::GBP.name(), "Pound Sterling");
+    }
+}
@@ -1,15 +1,78 @@
+use crate::error::Error;
 use crate::item_state::ItemState;
-use serde::Deserialize;
+use crate::timestamp::Timestamp;
+
+/// Value stored behind a key prefix, parsed with an error naming the attribute.
+pub trait FromPrefixed: Sized {
+    fn from_prefixed(value: &str, field: &'static str) -> Result<Self, Error>;
+}
+
+impl FromPrefixed for String {
+    fn from_prefixed(value: &str, _field: &'static str) -> Result<Self, Error> {
+        Ok(value.to_string())
+    }
+}
+
+impl FromPrefixed for ItemState {
+    fn from_prefixed(value: &str, field: &'static str) -> Result<Self, Error> {
+        value.parse().map_err(|_| Error::UnparsableEnum {
+            field,
+            type_name: "ItemState",
+            value: value.to_string(),
+        })
+    }
+}
+
+impl FromPrefixed for Timestamp {
+    fn from_prefixed(value: &str, _field: &'static str) -> Result<Self, Error> {
+        Ok(value.parse()?)
+    }
+}
+
+/// Strips `prefix` from the stored `value` of attribute `field` and parses the rest.
+pub fn strip_prefix<T: FromPrefixed>(
+    value: &str,
+    prefix: &'static str,
+    field: &'static str,
+) -> Result<T, Error> {
+    let stripped = value
+        .strip_prefix(prefix)
+        .ok_or_else(|| Error::MissingPrefix {
+            field,
+            prefix,
+            value: value.to_string(),
+        })?;
+    T::from_prefixed(stripped, field)
+}
 
 #[macro_export]
 macro_rules! make_opt_prefix_fns {
     (
+        $(#[$attr:meta])*
         ser = $ser_fn:ident,
         de = $de_fn:ident,
         ty = $ty:ty,
         prefix = $prefix:expr
+    ) => {
+        $crate::make_opt_prefix_fns!(
+            $(#[$attr])*
+            ser = $ser_fn,
+            de = $de_fn,
+            ty = $ty,
+            prefix = $prefix,
+            field = "attribute"
+        );
+    };
+    (
+        $(#[$attr:meta])*
+        ser = $ser_fn:ident,
+        de = $de_fn:ident,
+        ty = $ty:ty,
+        prefix = $prefix:expr,
+        field = $field:expr
     ) => {
         // Option<T>
+        $(#[$attr])*
         pub fn $ser_fn<S>(val: &Option<$ty>, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: serde::Serializer,
@@ -23,6 +86,7 @@ macro_rules! make_opt_prefix_fns {
             }
         }
 
+        $(#[$attr])*
         pub fn $de_fn<'de, D>(deserializer: D) -> Result<Option<$ty>, D::Error>
         where
             D: serde::Deserializer<'de>,
@@ -36,7 +100,7 @@ macro_rules! make_opt_prefix_fns {
                     f.write_str(concat!(
                         "optional string with prefix '",
                         $prefix,
-                        "' and valid enum variant"
+                        "' and valid value"
                     ))
                 }
 
@@ -44,13 +108,10 @@ macro_rules! make_opt_prefix_fns {
                 where
                     D: serde::Deserializer<'de>,
                 {
-                    let s: String = Deserialize::deserialize(deserializer)?;
-                    let stripped = s.strip_prefix($prefix).ok_or_else(|| {
-                        serde::de::Error::custom(concat!("missing prefix '", $prefix, "'"))
-                    })?;
-                    stripped.parse::<$ty>().map(Some).map_err(|_| {
-                        serde::de::Error::custom(concat!("failed to parse enum from string"))
-                    })
+                    let s: String = serde::Deserialize::deserialize(deserializer)?;
+                    $crate::ddb_prefix::strip_prefix::<$ty>(&s, $prefix, $field)
+                        .map(Some)
+                        .map_err(serde::de::Error::custom)
                 }
 
                 fn visit_none<E>(self) -> Result<Self::Value, E>
@@ -69,12 +130,31 @@ macro_rules! make_opt_prefix_fns {
 #[macro_export]
 macro_rules! make_prefix_fns {
     (
+        $(#[$attr:meta])*
         ser = $ser_fn:ident,
         de = $de_fn:ident,
         ty = $ty:ty,
         prefix = $prefix:expr
+    ) => {
+        $crate::make_prefix_fns!(
+            $(#[$attr])*
+            ser = $ser_fn,
+            de = $de_fn,
+            ty = $ty,
+            prefix = $prefix,
+            field = "attribute"
+        );
+    };
+    (
+        $(#[$attr:meta])*
+        ser = $ser_fn:ident,
+        de = $de_fn:ident,
+        ty = $ty:ty,
+        prefix = $prefix:expr,
+        field = $field:expr
     ) => {
         // Plain T
+        $(#[$attr])*
         pub fn $ser_fn<S>(val: &$ty, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: serde::Serializer,
@@ -83,6 +163,7 @@ macro_rules! make_prefix_fns {
             serializer.serialize_str(&s)
         }
 
+        $(#[$attr])*
         pub fn $de_fn<'de, D>(deserializer: D) -> Result<$ty, D::Error>
         where
             D: serde::Deserializer<'de>,
@@ -96,7 +177,7 @@ macro_rules! make_prefix_fns {
                     f.write_str(concat!(
                         "string with prefix '",
                         $prefix,
-                        "' and valid enum variant"
+                        "' and valid value"
                     ))
                 }
 
@@ -104,12 +185,8 @@ macro_rules! make_prefix_fns {
                 where
                     E: serde::de::Error,
                 {
-                    let stripped = value
-                        .strip_prefix($prefix)
-                        .ok_or_else(|| E::custom(concat!("missing prefix '", $prefix, "'")))?;
-                    stripped
-                        .parse::<$ty>()
-                        .map_err(|_| E::custom("failed to parse enum from string"))
+                    $crate::ddb_prefix::strip_prefix::<$ty>(value, $prefix, $field)
+                        .map_err(E::custom)
                 }
             }
 
@@ -120,7 +197,95 @@ macro_rules! make_prefix_fns {
 
 // region macro_gen
 
+// one pair per attribute and named after it, since errors report its name
+
+make_prefix_fns!(
+    ser = ser_pk_item_prefix,
+    de = de_pk_item_prefix,
+    ty = String,
+    prefix = "item#",
+    field = "pk"
+);
+
+make_prefix_fns!(
+    ser = ser_party_id_source_prefix,
+    de = de_party_id_source_prefix,
+    ty = String,
+    prefix = "source#",
+    field = "party_id"
+);
+
+make_prefix_fns!(
+    ser = ser_event_id_item_prefix,
+    de = de_event_id_item_prefix,
+    ty = String,
+    prefix = "item#",
+    field = "event_id"
+);
+
 make_prefix_fns!(
+    ser = ser_sk_snapshot_prefix,
+    de = de_sk_snapshot_prefix,
+    ty = Timestamp,
+    prefix = "item#snapshot#",
+    field = "sk"
+);
+
+make_opt_prefix_fns!(
+    ser = ser_opt_event_id_item_prefix,
+    de = de_opt_event_id_item_prefix,
+    ty = String,
+    prefix = "item#",
+    field = "event_id"
+);
+
+make_opt_prefix_fns!(
+    ser = ser_opt_party_id_source_prefix,
+    de = de_opt_party_id_source_prefix,
+    ty = String,
+    prefix = "source#",
+    field = "party_id"
+);
+
+make_opt_prefix_fns!(
+    ser = ser_opt_state_item_prefix,
+    de = de_opt_state_item_prefix,
+    ty = ItemState,
+    prefix = "item#",
+    field = "state"
+);
+
+// item-event sort-keys share their prefix with the snapshot ones, which are rejected
+
+pub fn ser_opt_sk_item_prefix<S>(val: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match val {
+        Some(v) => serializer.serialize_str(&format!("item#{v}")),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn de_opt_sk_item_prefix<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let sk: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    sk.map(|sk| {
+        if sk.starts_with("item#snapshot#") {
+            return Err(Error::SnapshotSortKey { value: sk });
+        }
+        strip_prefix(&sk, "item#", "sk")
+    })
+    .transpose()
+    .map_err(serde::de::Error::custom)
+}
+
+// field-agnostic helpers, whose errors cannot name the attribute
+
+make_prefix_fns!(
+    #[deprecated(note = "use `ser_pk_item_prefix`/`de_pk_item_prefix` or the `event_id` pair")]
     ser = ser_string_item_prefix,
     de = de_string_item_prefix,
     ty = String,
@@ -128,6 +293,7 @@ make_prefix_fns!(
 );
 
 make_prefix_fns!(
+    #[deprecated(note = "use `ser_party_id_source_prefix`/`de_party_id_source_prefix`")]
     ser = ser_string_source_prefix,
     de = de_string_source_prefix,
     ty = String,
@@ -135,6 +301,7 @@ make_prefix_fns!(
 );
 
 make_opt_prefix_fns!(
+    #[deprecated(note = "use `ser_opt_event_id_item_prefix`/`de_opt_event_id_item_prefix`")]
     ser = ser_opt_string_item_prefix,
     de = de_opt_string_item_prefix,
     ty = String,
@@ -142,6 +309,7 @@ make_opt_prefix_fns!(
 );
 
 make_opt_prefix_fns!(
+    #[deprecated(note = "use `ser_opt_party_id_source_prefix`/`de_opt_party_id_source_prefix`")]
     ser = ser_opt_string_source_prefix,
     de = de_opt_string_source_prefix,
     ty = String,
@@ -149,6 +317,7 @@ make_opt_prefix_fns!(
 );
 
 make_opt_prefix_fns!(
+    #[deprecated(note = "use `ser_opt_state_item_prefix`/`de_opt_state_item_prefix`")]
     ser = ser_opt_item_state_item_prefix,
     de = de_opt_item_state_item_prefix,
     ty = ItemState,
@@ -156,3 +325,85 @@ make_opt_prefix_fns!(
 );
 
 // endregion
+
+#[cfg(test)]
+mod tests {
+    use crate::ddb_prefix::strip_prefix;
+    use crate::error::Error;
+    use crate::item_state::ItemState;
+    use crate::timestamp::{ParseTimestampError, Timestamp};
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn should_name_prefix_and_field_when_prefix_is_missing() {
+        let actual = strip_prefix::<String>("123456", "item#", "pk");
+
+        assert_eq!(
+            actual,
+            Err(Error::MissingPrefix {
+                field: "pk",
+                prefix: "item#",
+                value: "123456".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_report_unparsable_enum_value() {
+        let actual = strip_prefix::<ItemState>("item#GONE", "item#", "state");
+
+        assert_eq!(
+            actual,
+            Err(Error::UnparsableEnum {
+                field: "state",
+                type_name: "ItemState",
+                value: "GONE".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn should_keep_field_agnostic_helpers() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Stored {
+            #[serde(
+                serialize_with = "crate::ddb_prefix::ser_string_item_prefix",
+                deserialize_with = "crate::ddb_prefix::de_string_item_prefix"
+            )]
+            pk: String,
+            #[serde(
+                serialize_with = "crate::ddb_prefix::ser_opt_item_state_item_prefix",
+                deserialize_with = "crate::ddb_prefix::de_opt_item_state_item_prefix"
+            )]
+            state: Option<ItemState>,
+        }
+        let stored = Stored {
+            pk: "123456".to_string(),
+            state: Some(ItemState::SOLD),
+        };
+
+        let actual = serde_json::to_string(&stored).unwrap();
+
+        assert_eq!(actual, r#"{"pk":"item#123456","state":"item#SOLD"}"#);
+        assert_eq!(serde_json::from_str::<Stored>(&actual).unwrap(), stored);
+        let error = serde_json::from_str::<Stored>(r#"{"pk":"123456","state":null}"#).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("'attribute' value '123456' lacks prefix 'item#'")
+        );
+    }
+
+    #[test]
+    fn should_report_timestamp_error() {
+        let actual = strip_prefix::<Timestamp>("item#yesterday", "item#", "sk");
+
+        assert_eq!(
+            actual,
+            Err(Error::Timestamp(ParseTimestampError::Malformed(
+                "yesterday".to_string()
+            )))
+        );
+    }
+}
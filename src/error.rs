@@ -0,0 +1,162 @@
+use crate::price::Currency;
+use crate::timestamp::{ParseTimestampError, Timestamp};
+use std::fmt;
+
+/// Why an `ItemData` record could not be converted into an `ItemModel`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ItemError {
+    /// The clock's reading is no valid [`Timestamp`].
+    Clock(ParseTimestampError),
+    InvalidTimestamp(ParseTimestampError),
+    /// The price could not be converted to EUR with the rate valid at `at`.
+    CurrencyConversion {
+        currency: Currency,
+        at: Timestamp,
+    },
+    InvalidId {
+        field: &'static str,
+        value: String,
+    },
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemError::Clock(e) => write!(f, "clock returned an invalid instant: {e}"),
+            ItemError::InvalidTimestamp(e) => write!(f, "invalid timestamp: {e}"),
+            ItemError::CurrencyConversion { currency, at } => {
+                write!(f, "cannot convert {currency} to EUR at {at}")
+            }
+            ItemError::InvalidId { field, value } => write!(f, "invalid {field} '{value}'"),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ItemError::Clock(e) | ItemError::InvalidTimestamp(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseTimestampError> for ItemError {
+    fn from(e: ParseTimestampError) -> Self {
+        ItemError::InvalidTimestamp(e)
+    }
+}
+
+/// Crate-wide error for materializing item-events and reading stored attributes.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Error {
+    /// There were no item-events to materialize.
+    EmptyEvents,
+    /// The item-event at `index` belongs to another item than the first one.
+    MismatchedItemIds {
+        index: usize,
+        expected: String,
+        found: String,
+        event_id: Option<String>,
+    },
+    /// The item-event at `index` has no `created`, which strict materialization requires.
+    MissingCreated {
+        index: usize,
+        event_id: Option<String>,
+    },
+    /// The item-event at `index` is out of order relative to its predecessor, e.g.
+    /// newer although item-events must be sorted by latest (first).
+    UnsortedEvents {
+        index: usize,
+        event_id: Option<String>,
+        created: Timestamp,
+        previous: Timestamp,
+    },
+    /// A stored attribute lacks its key prefix, e.g. `item#`.
+    MissingPrefix {
+        field: &'static str,
+        prefix: &'static str,
+        value: String,
+    },
+    /// A stored `sk` is the `item#snapshot#` sort-key of an item snapshot, where an
+    /// item-event was expected.
+    SnapshotSortKey {
+        value: String,
+    },
+    UnparsableEnum {
+        field: &'static str,
+        type_name: &'static str,
+        value: String,
+    },
+    Timestamp(ParseTimestampError),
+    Item(ItemError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EmptyEvents => f.write_str("no item-events given"),
+            Error::MismatchedItemIds {
+                index,
+                expected,
+                found,
+                event_id,
+            } => write!(
+                f,
+                "item-event #{index} {event_id:?} belongs to item '{found}', expected '{expected}'"
+            ),
+            Error::MissingCreated { index, event_id } => {
+                write!(f, "item-event #{index} {event_id:?} has no 'created'")
+            }
+            Error::UnsortedEvents {
+                index,
+                event_id,
+                created,
+                previous,
+            } => write!(
+                f,
+                "item-event #{index} {event_id:?} created {created} is out of order after its predecessor created {previous}"
+            ),
+            Error::MissingPrefix {
+                field,
+                prefix,
+                value,
+            } => write!(f, "'{field}' value '{value}' lacks prefix '{prefix}'"),
+            Error::SnapshotSortKey { value } => {
+                write!(
+                    f,
+                    "'sk' value '{value}' belongs to a snapshot, not an item-event"
+                )
+            }
+            Error::UnparsableEnum {
+                field,
+                type_name,
+                value,
+            } => write!(f, "'{field}' value '{value}' is no valid {type_name}"),
+            Error::Timestamp(e) => e.fmt(f),
+            Error::Item(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Timestamp(e) => Some(e),
+            Error::Item(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseTimestampError> for Error {
+    fn from(e: ParseTimestampError) -> Self {
+        Error::Timestamp(e)
+    }
+}
+
+impl From<ItemError> for Error {
+    fn from(e: ItemError) -> Self {
+        Error::Item(e)
+    }
+}
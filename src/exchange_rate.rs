@@ -0,0 +1,340 @@
+use crate::money::{Money, RoundingMode};
+use crate::price::Currency;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, Time};
+
+/// Fractional digits kept when a cross rate has to be derived by division.
+pub const CROSS_RATE_SCALE: u8 = 10;
+
+pub trait ExchangeRateProvider {
+    /// How many units of `to` one unit of `from` was worth at `at`.
+    /// `None` if no rate is known for that pair and instant.
+    fn rate(&self, from: Currency, to: Currency, at: OffsetDateTime) -> Option<Money>;
+}
+
+/// Time-independent rates, stated as the value of one unit of each currency in euros.
+pub struct FixedEuroRates {
+    euro_value: fn(Currency) -> Option<Money>,
+}
+
+impl FixedEuroRates {
+    pub const fn new(euro_value: fn(Currency) -> Option<Money>) -> Self {
+        FixedEuroRates { euro_value }
+    }
+}
+
+impl ExchangeRateProvider for FixedEuroRates {
+    fn rate(&self, from: Currency, to: Currency, _at: OffsetDateTime) -> Option<Money> {
+        if from == to {
+            return Some(Money::from_major(1));
+        }
+        let from_in_eur = (self.euro_value)(from)?;
+        if to == Currency::EUR {
+            return Some(from_in_eur);
+        }
+        from_in_eur.checked_div(
+            (self.euro_value)(to)?,
+            CROSS_RATE_SCALE,
+            RoundingMode::HalfEven,
+        )
+    }
+}
+
+pub const DEFAULT_EXCHANGE_RATES: FixedEuroRates = FixedEuroRates::new(|currency| match currency {
+    Currency::EUR => Some(Money::from_major(1)),
+    Currency::GBP => Some(Money::new(117, 2)),
+    Currency::USD => Some(Money::new(9, 1)),
+    Currency::AUD => Some(Money::new(58, 2)),
+    Currency::CAD => Some(Money::new(67, 2)),
+    Currency::NZD => Some(Money::new(53, 2)),
+    _ => None,
+});
+
+/// Dated rate history quoted against a single base currency, the way the ECB publishes
+/// them: one unit of `base` is worth `quote` units of the currency, valid from the given
+/// instant until the next quote for that currency.
+#[derive(Clone, Debug)]
+pub struct ExchangeRateTable {
+    base: Currency,
+    quotes: HashMap<Currency, BTreeMap<OffsetDateTime, Money>>,
+}
+
+impl ExchangeRateTable {
+    pub fn new(base: Currency) -> Self {
+        ExchangeRateTable {
+            base,
+            quotes: HashMap::new(),
+        }
+    }
+
+    pub fn base(&self) -> Currency {
+        self.base
+    }
+
+    pub fn insert(
+        &mut self,
+        currency: Currency,
+        valid_from: OffsetDateTime,
+        quote: Money,
+    ) -> &mut Self {
+        self.quotes
+            .entry(currency)
+            .or_default()
+            .insert(valid_from, quote);
+        self
+    }
+
+    /// Units of `currency` per unit of the base currency at `at`.
+    pub fn quote(&self, currency: Currency, at: OffsetDateTime) -> Option<Money> {
+        if currency == self.base {
+            return Some(Money::from_major(1));
+        }
+        self.quotes
+            .get(&currency)?
+            .range(..=at)
+            .next_back()
+            .map(|(_, quote)| *quote)
+    }
+
+    /// Parses the ECB eurofxref CSV format (`eurofxref.csv` / `eurofxref-hist.csv`):
+    /// a `Date,USD,JPY,...` header followed by one row per day.
+    /// Unknown currencies and `N/A` cells are skipped.
+    pub fn from_ecb_csv(csv: &str) -> Result<Self, RateHistoryError> {
+        let mut table = ExchangeRateTable::new(Currency::EUR);
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (_, header) = lines
+            .next()
+            .ok_or_else(|| RateHistoryError::malformed(1, "missing header"))?;
+        let currencies: Vec<Option<Currency>> = header
+            .split(',')
+            .skip(1)
+            .map(|code| code.trim().parse().ok())
+            .collect();
+
+        for (index, line) in lines {
+            let line_number = index + 1;
+            let mut cells = line.split(',');
+            let valid_from = parse_ecb_date(cells.next().unwrap_or_default(), line_number)?;
+            for (currency, cell) in currencies.iter().zip(cells) {
+                let cell = cell.trim();
+                let Some(currency) = currency else { continue };
+                if cell.is_empty() || cell == "N/A" {
+                    continue;
+                }
+                let quote = cell.parse().map_err(|err| {
+                    RateHistoryError::malformed(line_number, format!("rate '{cell}': {err}"))
+                })?;
+                table.insert(*currency, valid_from, quote);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Parses the ECB eurofxref XML format (`eurofxref-daily.xml` / `eurofxref-hist.xml`):
+    /// `<Cube time="...">` elements containing `<Cube currency="..." rate="..."/>` elements.
+    /// Unknown currencies are skipped.
+    pub fn from_ecb_xml(xml: &str) -> Result<Self, RateHistoryError> {
+        let mut table = ExchangeRateTable::new(Currency::EUR);
+        let mut valid_from = None;
+
+        for (index, line) in xml.lines().enumerate() {
+            let line_number = index + 1;
+            for element in line.split('<').filter(|e| e.starts_with("Cube")) {
+                if let Some(time) = xml_attribute(element, "time") {
+                    valid_from = Some(parse_ecb_date(time, line_number)?);
+                }
+                let (Some(code), Some(rate)) = (
+                    xml_attribute(element, "currency"),
+                    xml_attribute(element, "rate"),
+                ) else {
+                    continue;
+                };
+                let Ok(currency) = code.parse::<Currency>() else {
+                    continue;
+                };
+                let valid_from = valid_from.ok_or_else(|| {
+                    RateHistoryError::malformed(line_number, "rate outside of a dated Cube")
+                })?;
+                let quote = rate.parse().map_err(|err| {
+                    RateHistoryError::malformed(line_number, format!("rate '{rate}': {err}"))
+                })?;
+                table.insert(currency, valid_from, quote);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Loads an ECB rate history from disk; `.xml` files are read as XML, anything else as CSV.
+    pub fn from_ecb_file(path: impl AsRef<Path>) -> Result<Self, RateHistoryError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(RateHistoryError::Io)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("xml") => Self::from_ecb_xml(&content),
+            _ => Self::from_ecb_csv(&content),
+        }
+    }
+}
+
+impl ExchangeRateProvider for ExchangeRateTable {
+    fn rate(&self, from: Currency, to: Currency, at: OffsetDateTime) -> Option<Money> {
+        if from == to {
+            return Some(Money::from_major(1));
+        }
+        let from_quote = self.quote(from, at)?;
+        let to_quote = self.quote(to, at)?;
+        if from == self.base {
+            return Some(to_quote);
+        }
+        to_quote.checked_div(from_quote, CROSS_RATE_SCALE, RoundingMode::HalfEven)
+    }
+}
+
+#[derive(Debug)]
+pub enum RateHistoryError {
+    Io(std::io::Error),
+    Malformed { line: usize, reason: String },
+}
+
+impl RateHistoryError {
+    fn malformed(line: usize, reason: impl Into<String>) -> Self {
+        RateHistoryError::Malformed {
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for RateHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateHistoryError::Io(err) => write!(f, "failed to read rate history: {err}"),
+            RateHistoryError::Malformed { line, reason } => {
+                write!(f, "malformed rate history at line {line}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RateHistoryError {}
+
+fn parse_ecb_date(value: &str, line: usize) -> Result<OffsetDateTime, RateHistoryError> {
+    Date::parse(value.trim(), format_description!("[year]-[month]-[day]"))
+        .map(|date| date.with_time(Time::MIDNIGHT).assume_utc())
+        .map_err(|err| RateHistoryError::malformed(line, format!("date '{value}': {err}")))
+}
+
+fn xml_attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!(" {name}="))? + name.len() + 2;
+    let quote = element[start..].chars().next()?;
+    let value = &element[start + 1..];
+    value.find(quote).map(|end| &value[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exchange_rate::{DEFAULT_EXCHANGE_RATES, ExchangeRateProvider, ExchangeRateTable};
+    use crate::money::Money;
+    use crate::price::Currency::{CHF, EUR, GBP, USD};
+    use time::macros::datetime;
+
+    const ECB_CSV: &str = "Date, USD, JPY, GBP, \n\
+        2024-01-05, 1.0921, 159.52, 0.86, \n\
+        2024-01-04, 1.0953, 158.38, 0.8615, \n";
+
+    const ECB_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+	<Cube>
+		<Cube time='2024-01-05'>
+			<Cube currency='USD' rate='1.0921'/>
+			<Cube currency='JPY' rate='159.52'/>
+			<Cube currency='GBP' rate='0.86'/>
+		</Cube>
+		<Cube time='2024-01-04'>
+			<Cube currency='USD' rate='1.0953'/>
+			<Cube currency='GBP' rate='0.8615'/>
+		</Cube>
+	</Cube>
+</gesmes:Envelope>"#;
+
+    #[test]
+    fn should_answer_identity_rate() {
+        let table = ExchangeRateTable::new(EUR);
+        let actual = table.rate(USD, USD, datetime!(2024-01-05 12:00 UTC));
+        assert_eq!(actual, Some(Money::from_major(1)));
+    }
+
+    #[test]
+    fn should_use_rate_valid_at_instant() {
+        let table = ExchangeRateTable::from_ecb_csv(ECB_CSV).unwrap();
+
+        assert_eq!(
+            table.rate(EUR, USD, datetime!(2024-01-04 23:59 UTC)),
+            Some(Money::new(10953, 4))
+        );
+        assert_eq!(
+            table.rate(EUR, USD, datetime!(2024-01-05 00:00 UTC)),
+            Some(Money::new(10921, 4))
+        );
+        assert_eq!(
+            table.rate(EUR, USD, datetime!(2030-01-01 00:00 UTC)),
+            Some(Money::new(10921, 4))
+        );
+    }
+
+    #[test]
+    fn should_not_answer_before_first_quote() {
+        let table = ExchangeRateTable::from_ecb_csv(ECB_CSV).unwrap();
+        assert_eq!(table.rate(EUR, USD, datetime!(2024-01-03 12:00 UTC)), None);
+    }
+
+    #[test]
+    fn should_derive_inverse_and_cross_rates() {
+        let table = ExchangeRateTable::from_ecb_csv(ECB_CSV).unwrap();
+        let at = datetime!(2024-01-05 12:00 UTC);
+
+        assert_eq!(table.rate(GBP, EUR, at), Some(Money::new(11627906977, 10)));
+        assert_eq!(table.rate(GBP, USD, at), Some(Money::new(12698837209, 10)));
+    }
+
+    #[test]
+    fn should_parse_ecb_xml_like_csv() {
+        let from_xml = ExchangeRateTable::from_ecb_xml(ECB_XML).unwrap();
+        let from_csv = ExchangeRateTable::from_ecb_csv(ECB_CSV).unwrap();
+
+        for at in [
+            datetime!(2024-01-04 12:00 UTC),
+            datetime!(2024-01-05 12:00 UTC),
+        ] {
+            for currency in [USD, GBP] {
+                assert_eq!(
+                    from_xml.rate(EUR, currency, at),
+                    from_csv.rate(EUR, currency, at)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_reject_malformed_csv() {
+        let csv = "Date,USD\n2024-13-01,1.0921\n";
+        assert!(ExchangeRateTable::from_ecb_csv(csv).is_err());
+    }
+
+    #[test]
+    fn should_not_answer_unknown_default_rate() {
+        let actual = DEFAULT_EXCHANGE_RATES.rate(CHF, EUR, datetime!(2024-01-05 12:00 UTC));
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn should_keep_default_rates_exact_towards_eur() {
+        let actual = DEFAULT_EXCHANGE_RATES.rate(GBP, EUR, datetime!(2024-01-05 12:00 UTC));
+        assert_eq!(actual, Some(Money::new(117, 2)));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::error::Error;
+use crate::item_model::ItemModel;
+use crate::timestamp::Timestamp;
+
+/// Order in which an [`ItemAggregate`] receives item-events.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FoldOrder {
+    OldestFirst,
+    NewestFirst,
+}
+
+/// Materializes an item by folding its item-events one at a time, e.g. while
+/// streaming them, instead of loading the whole history into a slice.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ItemAggregate {
+    order: FoldOrder,
+    // unresolved, so cleared attributes keep shadowing older events
+    state: Option<ItemModel>,
+    // created of the last applied item-event, which the next one must continue from
+    last_created: Option<Timestamp>,
+    applied: usize,
+}
+
+impl ItemAggregate {
+    pub fn new(order: FoldOrder) -> Self {
+        ItemAggregate {
+            order,
+            state: None,
+            last_created: None,
+            applied: 0,
+        }
+    }
+
+    /// Continues from a stored snapshot - an item materialized earlier - with newer
+    /// item-events, oldest first.
+    pub fn resume(snapshot: ItemModel) -> Self {
+        ItemAggregate {
+            order: FoldOrder::OldestFirst,
+            last_created: snapshot.created,
+            state: Some(snapshot),
+            applied: 0,
+        }
+    }
+
+    pub fn order(&self) -> FoldOrder {
+        self.order
+    }
+
+    /// Number of item-events applied since creation or resumption.
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    /// Folds in the next item-event. It must belong to the same item and continue
+    /// the fold order by its `created`.
+    pub fn apply(&mut self, event: &ItemModel) -> Result<&mut Self, Error> {
+        let index = self.applied;
+        let Some(created) = event.created else {
+            return Err(Error::MissingCreated {
+                index,
+                event_id: event.event_id.clone(),
+            });
+        };
+        let state = match self.state.take() {
+            None => event.clone(),
+            Some(state) => {
+                let checked = self.check(&state, event, index, created);
+                if let Err(e) = checked {
+                    self.state = Some(state);
+                    return Err(e);
+                }
+                match self.order {
+                    FoldOrder::OldestFirst => event.clone().merged_over(state),
+                    FoldOrder::NewestFirst => state.merged_over(event.clone()),
+                }
+            }
+        };
+        self.state = Some(state);
+        self.last_created = Some(created);
+        self.applied += 1;
+        Ok(self)
+    }
+
+    /// The materialized item, or `None` before the first item-event.
+    pub fn item(&self) -> Option<ItemModel> {
+        self.state.clone().map(ItemModel::resolved)
+    }
+
+    pub fn into_item(self) -> Option<ItemModel> {
+        self.state.map(ItemModel::resolved)
+    }
+
+    fn check(
+        &self,
+        state: &ItemModel,
+        event: &ItemModel,
+        index: usize,
+        created: Timestamp,
+    ) -> Result<(), Error> {
+        if state.item_id != event.item_id {
+            return Err(Error::MismatchedItemIds {
+                index,
+                expected: state.item_id.clone(),
+                found: event.item_id.clone(),
+                event_id: event.event_id.clone(),
+            });
+        }
+        let previous = match self.order {
+            FoldOrder::OldestFirst => self.last_created.filter(|previous| created < *previous),
+            FoldOrder::NewestFirst => self.last_created.filter(|previous| created > *previous),
+        };
+        match previous {
+            Some(previous) => Err(Error::UnsortedEvents {
+                index,
+                event_id: event.event_id.clone(),
+                created,
+                previous,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::item_aggregate::{FoldOrder, ItemAggregate};
+    use crate::item_model::ItemModel;
+    use crate::item_state::ItemState;
+    use crate::money::Money;
+    use crate::patch::Patch;
+    use crate::test_support::event;
+    use rstest::rstest;
+
+    fn item_events_newest_first() -> Vec<ItemModel> {
+        let mut cleared = event("2010-01-03T11:00:00.001Z")
+            .state(ItemState::SOLD)
+            .to_owned();
+        cleared.image_url = Patch::Cleared;
+        vec![
+            cleared,
+            event("2010-01-02T11:00:00.001Z")
+                .state(ItemState::AVAILABLE)
+                .price(Money::from_major(37))
+                .to_owned(),
+            event("2010-01-01T11:00:00.001Z")
+                .state(ItemState::LISTED)
+                .price(Money::from_major(42))
+                .category("foo".to_string())
+                .image_url("https://foo.bar?item_img=123456".to_string())
+                .to_owned(),
+        ]
+    }
+
+    #[rstest]
+    #[case(FoldOrder::NewestFirst)]
+    #[case(FoldOrder::OldestFirst)]
+    fn should_fold_like_slice_materialization(#[case] order: FoldOrder) {
+        let item_events = item_events_newest_first();
+        let expected = ItemModel::try_from(&item_events[..]).unwrap();
+        let mut aggregate = ItemAggregate::new(order);
+
+        let mut ordered = item_events.clone();
+        if order == FoldOrder::OldestFirst {
+            ordered.reverse();
+        }
+        for item_event in &ordered {
+            aggregate.apply(item_event).unwrap();
+        }
+
+        assert_eq!(aggregate.applied(), 3);
+        assert_eq!(aggregate.item(), Some(expected));
+    }
+
+    #[test]
+    fn should_resume_from_snapshot() {
+        let item_events = item_events_newest_first();
+        let snapshot = ItemModel::try_from(&item_events[1..]).unwrap();
+        let stored = serde_json::to_string(&snapshot).unwrap();
+
+        let mut aggregate = ItemAggregate::resume(serde_json::from_str(&stored).unwrap());
+        aggregate.apply(&item_events[0]).unwrap();
+
+        assert_eq!(
+            aggregate.into_item(),
+            Some(ItemModel::try_from(&item_events[..]).unwrap())
+        );
+    }
+
+    #[test]
+    fn should_reject_event_out_of_fold_order() {
+        let item_events = item_events_newest_first();
+        let mut aggregate = ItemAggregate::new(FoldOrder::OldestFirst);
+        aggregate.apply(&item_events[1]).unwrap();
+
+        let actual = aggregate.apply(&item_events[2]).map(|_| ());
+
+        assert_eq!(
+            actual,
+            Err(Error::UnsortedEvents {
+                index: 1,
+                event_id: Some("foo#123456#2010-01-01T11:00:00.001Z".to_string()),
+                created: "2010-01-01T11:00:00.001Z".parse().unwrap(),
+                previous: "2010-01-02T11:00:00.001Z".parse().unwrap(),
+            })
+        );
+        assert_eq!(aggregate.applied(), 1);
+        assert_eq!(aggregate.item().unwrap().price, Some(Money::from_major(37)));
+    }
+
+    #[test]
+    fn should_check_against_last_applied_event_newest_first() {
+        let item_events = item_events_newest_first();
+        let mut aggregate = ItemAggregate::new(FoldOrder::NewestFirst);
+        aggregate.apply(&item_events[0]).unwrap();
+        aggregate.apply(&item_events[2]).unwrap();
+
+        let actual = aggregate.apply(&item_events[1]).map(|_| ());
+
+        assert_eq!(
+            actual,
+            Err(Error::UnsortedEvents {
+                index: 2,
+                event_id: Some("foo#123456#2010-01-02T11:00:00.001Z".to_string()),
+                created: "2010-01-02T11:00:00.001Z".parse().unwrap(),
+                previous: "2010-01-01T11:00:00.001Z".parse().unwrap(),
+            })
+        );
+        assert_eq!(aggregate.item().unwrap().price, Some(Money::from_major(42)));
+    }
+
+    #[test]
+    fn should_reject_event_of_other_item() {
+        let mut aggregate = ItemAggregate::new(FoldOrder::NewestFirst);
+        aggregate.apply(&event("2010-01-02T11:00:00.001Z")).unwrap();
+        let other = ItemModel::new("foo#654321".to_string())
+            .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+            .to_owned();
+
+        let actual = aggregate.apply(&other).map(|_| ());
+
+        assert!(matches!(
+            actual,
+            Err(Error::MismatchedItemIds { index: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,324 @@
+use crate::error::Error;
+use crate::item_model::{ItemModel, Materialization};
+use crate::item_state::ItemState;
+use crate::language::Language;
+use crate::money::Money;
+use crate::timestamp::Timestamp;
+use std::fmt;
+use strum::IntoEnumIterator;
+
+/// Change of one attribute of a materialized item. `from` is `None` if the
+/// attribute had no value before, `to` is `None` if it was cleared.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum FieldChange {
+    State {
+        from: Option<ItemState>,
+        to: ItemState,
+    },
+    // EUR
+    Price {
+        from: Option<Money>,
+        to: Money,
+    },
+    Category {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Name {
+        language: Language,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Description {
+        language: Language,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Url {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    ImageUrl {
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldChange::State { from: None, to } => write!(f, "state set to {to}"),
+            FieldChange::State {
+                from: Some(from),
+                to,
+            } => {
+                write!(f, "state changed {from} → {to}")
+            }
+            FieldChange::Price { from: None, to } => write!(f, "price set to {to} €"),
+            FieldChange::Price {
+                from: Some(from),
+                to,
+            } => {
+                let direction = if to < from { "dropped" } else { "rose" };
+                write!(f, "price {direction} from {from} € to {to} €")
+            }
+            FieldChange::Category { from, to } => write_text_change(f, "category", from, to),
+            FieldChange::Name { language, from, to } => {
+                write_text_change(f, &format!("name ({language})"), from, to)
+            }
+            FieldChange::Description { language, from, to } => {
+                write_text_change(f, &format!("description ({language})"), from, to)
+            }
+            FieldChange::Url { from, to } => write_text_change(f, "url", from, to),
+            FieldChange::ImageUrl { from, to } => write_text_change(f, "image url", from, to),
+        }
+    }
+}
+
+fn write_text_change(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    from: &Option<String>,
+    to: &Option<String>,
+) -> fmt::Result {
+    match (from, to) {
+        (None, Some(to)) => write!(f, "{label} set to '{to}'"),
+        (Some(from), Some(to)) => write!(f, "{label} changed from '{from}' to '{to}'"),
+        (Some(from), None) => write!(f, "{label} removed (was '{from}')"),
+        (None, None) => write!(f, "{label} unchanged"),
+    }
+}
+
+/// A [`FieldChange`] together with the item-event that made it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ChangeLogEntry {
+    pub event_id: Option<String>,
+    pub created: Timestamp,
+    pub change: FieldChange,
+}
+
+/// Changes made by each item-event of one item - sorted by latest (first), see
+/// [`Materialization::Strict`] - in chronological order. Attributes set by the
+/// oldest item-event count as changed from `None`; item-events repeating the
+/// current values make no changes.
+pub fn changelog(item_events: &[ItemModel]) -> Result<Vec<ChangeLogEntry>, Error> {
+    // validates item ids and order
+    ItemModel::materialize(item_events, Materialization::Strict)?;
+    let mut entries = Vec::new();
+    let mut before = ItemModel::new(item_events[0].item_id.clone());
+    // unresolved, so cleared attributes keep shadowing older events
+    let mut current: Option<ItemModel> = None;
+    for event in item_events.iter().rev() {
+        let next = match current {
+            Some(older) => event.clone().merged_over(older),
+            None => event.clone(),
+        };
+        let after = next.clone().resolved();
+        let created = event
+            .created
+            .expect("created of strictly sorted item-event");
+        entries.extend(
+            field_changes(&before, &after)
+                .into_iter()
+                .map(|change| ChangeLogEntry {
+                    event_id: event.event_id.clone(),
+                    created,
+                    change,
+                }),
+        );
+        before = after;
+        current = Some(next);
+    }
+    Ok(entries)
+}
+
+/// Changes between two materialized states of an item.
+fn field_changes(before: &ItemModel, after: &ItemModel) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    if let Some(to) = after.state.filter(|to| before.state != Some(*to)) {
+        changes.push(FieldChange::State {
+            from: before.state,
+            to,
+        });
+    }
+    if let Some(to) = after.price.filter(|to| before.price != Some(*to)) {
+        changes.push(FieldChange::Price {
+            from: before.price,
+            to,
+        });
+    }
+    if let Some((from, to)) = text_change(before.category.value(), after.category.value()) {
+        changes.push(FieldChange::Category { from, to });
+    }
+    for language in Language::iter() {
+        if let Some((from, to)) = text_change(before.name_lang(language), after.name_lang(language))
+        {
+            changes.push(FieldChange::Name { language, from, to });
+        }
+    }
+    for language in Language::iter() {
+        if let Some((from, to)) = text_change(
+            before.description_lang(language),
+            after.description_lang(language),
+        ) {
+            changes.push(FieldChange::Description { language, from, to });
+        }
+    }
+    if let Some((from, to)) = text_change(before.url.value(), after.url.value()) {
+        changes.push(FieldChange::Url { from, to });
+    }
+    if let Some((from, to)) = text_change(before.image_url.value(), after.image_url.value()) {
+        changes.push(FieldChange::ImageUrl { from, to });
+    }
+    changes
+}
+
+fn text_change(
+    from: Option<&String>,
+    to: Option<&String>,
+) -> Option<(Option<String>, Option<String>)> {
+    (from != to).then(|| (from.cloned(), to.cloned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::item_changelog::{ChangeLogEntry, FieldChange, changelog};
+    use crate::item_state::ItemState;
+    use crate::language::Language;
+    use crate::money::Money;
+    use crate::patch::Patch;
+    use crate::test_support::event;
+    use rstest::rstest;
+
+    #[test]
+    fn should_list_field_changes_chronologically() {
+        let mut cleared = event("2010-01-03T11:00:00.001Z")
+            .state(ItemState::SOLD)
+            .to_owned();
+        cleared.image_url = Patch::Cleared;
+        let item_events = [
+            cleared,
+            event("2010-01-02T11:00:00.001Z")
+                .state(ItemState::AVAILABLE)
+                .price(Money::from_major(37))
+                .name_de("Kommode".to_string())
+                .to_owned(),
+            event("2010-01-01T11:00:00.001Z")
+                .state(ItemState::AVAILABLE)
+                .price(Money::from_major(42))
+                .name_de("Komode".to_string())
+                .image_url("https://foo.bar?item_img=123456".to_string())
+                .to_owned(),
+        ];
+        let entry = |created: &str, change| ChangeLogEntry {
+            event_id: Some(format!("foo#123456#{created}")),
+            created: created.parse().unwrap(),
+            change,
+        };
+        let expected = vec![
+            entry(
+                "2010-01-01T11:00:00.001Z",
+                FieldChange::State {
+                    from: None,
+                    to: ItemState::AVAILABLE,
+                },
+            ),
+            entry(
+                "2010-01-01T11:00:00.001Z",
+                FieldChange::Price {
+                    from: None,
+                    to: Money::from_major(42),
+                },
+            ),
+            entry(
+                "2010-01-01T11:00:00.001Z",
+                FieldChange::Name {
+                    language: Language::DE,
+                    from: None,
+                    to: Some("Komode".to_string()),
+                },
+            ),
+            entry(
+                "2010-01-01T11:00:00.001Z",
+                FieldChange::ImageUrl {
+                    from: None,
+                    to: Some("https://foo.bar?item_img=123456".to_string()),
+                },
+            ),
+            entry(
+                "2010-01-02T11:00:00.001Z",
+                FieldChange::Price {
+                    from: Some(Money::from_major(42)),
+                    to: Money::from_major(37),
+                },
+            ),
+            entry(
+                "2010-01-02T11:00:00.001Z",
+                FieldChange::Name {
+                    language: Language::DE,
+                    from: Some("Komode".to_string()),
+                    to: Some("Kommode".to_string()),
+                },
+            ),
+            entry(
+                "2010-01-03T11:00:00.001Z",
+                FieldChange::State {
+                    from: Some(ItemState::AVAILABLE),
+                    to: ItemState::SOLD,
+                },
+            ),
+            entry(
+                "2010-01-03T11:00:00.001Z",
+                FieldChange::ImageUrl {
+                    from: Some("https://foo.bar?item_img=123456".to_string()),
+                    to: None,
+                },
+            ),
+        ];
+
+        let actual = changelog(&item_events).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_reject_unsorted_item_events() {
+        let item_events = [
+            event("2010-01-01T11:00:00.001Z"),
+            event("2010-01-02T11:00:00.001Z"),
+        ];
+
+        let actual = changelog(&item_events);
+
+        assert!(matches!(
+            actual,
+            Err(Error::UnsortedEvents { index: 1, .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(
+        FieldChange::Price { from: Some(Money::from_major(42)), to: Money::from_major(37) },
+        "price dropped from 42 € to 37 €"
+    )]
+    #[case(
+        FieldChange::Price { from: Some(Money::new(3700, 2)), to: Money::from_major(42) },
+        "price rose from 37.00 € to 42 €"
+    )]
+    #[case(
+        FieldChange::State { from: Some(ItemState::AVAILABLE), to: ItemState::SOLD },
+        "state changed AVAILABLE → SOLD"
+    )]
+    #[case(
+        FieldChange::Description { language: Language::EN, from: None, to: Some("bar".to_string()) },
+        "description (EN) set to 'bar'"
+    )]
+    #[case(
+        FieldChange::Url { from: Some("https://foo.bar".to_string()), to: None },
+        "url removed (was 'https://foo.bar')"
+    )]
+    fn should_describe_field_change(#[case] change: FieldChange, #[case] expected: &str) {
+        assert_eq!(change.to_string(), expected);
+    }
+}
@@ -1,22 +1,27 @@
-use crate::item_hash::{ItemHash, hash_item_details};
+use crate::clock::{Clock, SystemClock};
+use crate::error::ItemError;
+use crate::exchange_rate::{DEFAULT_EXCHANGE_RATES, ExchangeRateProvider};
+use crate::item_digest::ItemDigest;
+use crate::item_hash::{HashContent, HashInput, HashSpec, ItemHash};
 use crate::item_model::ItemModel;
 use crate::item_state::ItemState;
-use crate::language::Language::{DE, EN};
-use crate::language::{I18nString, Language};
+use crate::language::Language;
+use crate::localized_text::LocalizedText;
+use crate::money::{DEFAULT_ROUNDING_MODE, Money};
+use crate::patch::Patch;
 use crate::price::Price;
+use crate::timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use time::OffsetDateTime;
-use time::format_description::well_known::Rfc3339;
+use std::collections::BTreeSet;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct ItemData {
     #[serde(rename = "itemId")]
     pub item_id: String,
 
-    // ISO 8601: 2010-01-01T12:00:00.001+01:00
+    // RFC 3339, normalized to UTC: 2010-01-01T11:00:00.001Z
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub created: Option<String>,
+    pub created: Option<Timestamp>,
 
     #[serde(skip_serializing_if = "Option::is_none", rename = "sourceId", default)]
     pub source_id: Option<String>,
@@ -30,11 +35,20 @@ pub struct ItemData {
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub category: Option<String>,
 
-    #[serde(skip_serializing_if = "std::collections::HashMap::is_empty", default)]
-    pub name: I18nString,
+    // provenance stored next to the map as nameOriginal and nameMachineTranslated
+    #[serde(
+        flatten,
+        serialize_with = "crate::localized_text::ser_flat_name",
+        deserialize_with = "crate::localized_text::de_flat_name"
+    )]
+    pub name: LocalizedText,
 
-    #[serde(skip_serializing_if = "std::collections::HashMap::is_empty", default)]
-    pub description: I18nString,
+    #[serde(
+        flatten,
+        serialize_with = "crate::localized_text::ser_flat_description",
+        deserialize_with = "crate::localized_text::de_flat_description"
+    )]
+    pub description: LocalizedText,
 
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub url: Option<String>,
@@ -52,8 +66,8 @@ impl ItemData {
             state: None,
             price: None,
             category: None,
-            name: HashMap::new(),
-            description: HashMap::new(),
+            name: LocalizedText::new(),
+            description: LocalizedText::new(),
             url: None,
             image_url: None,
         }
@@ -66,11 +80,17 @@ impl ItemData {
         self
     }
 
-    pub fn created(&mut self, created: String) -> &mut Self {
+    pub fn created(&mut self, created: Timestamp) -> &mut Self {
         self.created = Some(created);
         self
     }
 
+    /// Sets `created` from a raw RFC 3339 string, e.g. as scraped.
+    pub fn try_created(&mut self, created: &str) -> Result<&mut Self, ItemError> {
+        self.created = Some(created.parse()?);
+        Ok(self)
+    }
+
     pub fn state(&mut self, state: ItemState) -> &mut Self {
         self.state = Some(state);
         self
@@ -86,7 +106,7 @@ impl ItemData {
         self
     }
 
-    pub fn name(&mut self, name: I18nString) -> &mut Self {
+    pub fn name(&mut self, name: LocalizedText) -> &mut Self {
         self.name = name;
         self
     }
@@ -96,7 +116,7 @@ impl ItemData {
         self
     }
 
-    pub fn description(&mut self, description: I18nString) -> &mut Self {
+    pub fn description(&mut self, description: LocalizedText) -> &mut Self {
         self.description = description;
         self
     }
@@ -119,87 +139,423 @@ impl ItemData {
     // endregion
 }
 
+/// Hashes the price converted to EUR as the `TryFrom` conversion does, with
+/// [`DEFAULT_EXCHANGE_RATES`] at `created`, or now if it is missing. A price those
+/// rates cannot convert hashes as missing, but then there is no converted model
+/// either; see [`ItemData::hash_with_rates`] for other providers.
+impl HashContent for ItemData {
+    fn hash_input(&self) -> HashInput<'_> {
+        let at = self.created.unwrap_or_else(Timestamp::now);
+        let price = self.eur_price(&DEFAULT_EXCHANGE_RATES, at).ok().flatten();
+        self.hash_input_with_price(price)
+    }
+}
+
 impl ItemHash for ItemData {
-    fn hash(&self) -> String {
-        hash_item_details(
-            self.state,
-            self.price.map(|price| price.def_amount_in_euros()),
-        )
+    fn hash(&self) -> ItemDigest {
+        self.hash_with(&HashSpec::CURRENT)
     }
 }
 
-impl Into<ItemModel> for ItemData {
-    fn into(self) -> ItemModel {
-        let created = self.created.or(OffsetDateTime::now_local()
-            .ok()
-            .map(|now| now.to_offset(time::UtcOffset::UTC).format(&Rfc3339).ok())
-            .flatten());
-        ItemModel {
-            item_id: self.item_id.clone(),
-            created: created.clone(),
+impl ItemData {
+    /// Hash under `spec` of the model [`ItemData::into_model_with`] converts with the
+    /// same `clock` and `rates`, so the price is converted to EUR as there.
+    pub fn hash_with_rates(
+        &self,
+        spec: &HashSpec,
+        clock: &dyn Clock,
+        rates: &dyn ExchangeRateProvider,
+    ) -> Result<ItemDigest, ItemError> {
+        let price = self.eur_price(rates, self.created_or_now(clock)?)?;
+        Ok(spec.hash(&self.hash_input_with_price(price)))
+    }
+
+    fn hash_input_with_price(&self, price: Option<Money>) -> HashInput<'_> {
+        HashInput {
+            state: self.state,
+            price,
+            category: self.category.as_deref(),
+            name: self
+                .name
+                .iter()
+                .map(|(lang, name)| (lang, name.as_str()))
+                .collect(),
+            description: self
+                .description
+                .iter()
+                .map(|(lang, description)| (lang, description.as_str()))
+                .collect(),
+            url: self.url.as_deref(),
+            image_url: self.image_url.as_deref(),
+        }
+    }
+
+    fn created_or_now(&self, clock: &dyn Clock) -> Result<Timestamp, ItemError> {
+        match self.created {
+            Some(created) => Ok(created),
+            None => clock.try_now().map_err(ItemError::Clock),
+        }
+    }
+
+    /// The price in EUR with the rate valid at `at`.
+    fn eur_price(
+        &self,
+        rates: &dyn ExchangeRateProvider,
+        at: Timestamp,
+    ) -> Result<Option<Money>, ItemError> {
+        self.price
+            .map(|price| {
+                price
+                    .amount_in_euros(rates, at.to_offset_date_time(), DEFAULT_ROUNDING_MODE)
+                    .ok_or(ItemError::CurrencyConversion {
+                        currency: price.currency,
+                        at,
+                    })
+            })
+            .transpose()
+    }
+
+    /// Like the `TryFrom` conversion, but normalizes the price to EUR with the rate
+    /// that was valid at `created` (or now, if `created` is missing).
+    pub fn into_model_with_rates(
+        self,
+        rates: &dyn ExchangeRateProvider,
+    ) -> Result<ItemModel, ItemError> {
+        self.into_model_with(&SystemClock, rates)
+    }
+
+    /// Like [`ItemData::into_model_with_rates`], but takes a missing `created` from `clock`.
+    pub fn into_model_with(
+        self,
+        clock: &dyn Clock,
+        rates: &dyn ExchangeRateProvider,
+    ) -> Result<ItemModel, ItemError> {
+        self.validate_ids()?;
+        let created = self.created_or_now(clock)?;
+        let eur_price = self.eur_price(rates, created)?;
+        let mut model = ItemModel {
+            created: Some(created),
             source_id: self.source_id,
-            event_id: Some(format!("{}#{}", self.item_id, created.unwrap())),
+            event_id: Some(format!("{}#{created}", self.item_id)),
             state: self.state,
-            price: self.price.map(|price| price.def_amount_in_euros()),
-            category: self.category,
-            name_en: self.name.get(&EN).map(|x| x.to_string()),
-            description_en: self.description.get(&EN).map(|x| x.to_string()),
-            name_de: self.name.get(&DE).map(|x| x.to_string()),
-            description_de: self.description.get(&DE).map(|x| x.to_string()),
-            url: self.url,
-            image_url: self.image_url,
-            hash: Some(hash_item_details(
-                self.state,
-                self.price.map(|price| price.def_amount_in_euros()),
-            )),
+            price: eur_price,
+            category: self.category.into(),
+            url: self.url.into(),
+            image_url: self.image_url.into(),
+            ..ItemModel::new(self.item_id)
+        };
+        model.name_original = self.name.original_language();
+        model.name_machine_translated = machine_translated_attr(&self.name);
+        model.description_original = self.description.original_language();
+        model.description_machine_translated = machine_translated_attr(&self.description);
+        for (lang, name) in self.name {
+            *model.name_attr(lang) = Patch::Set(name);
+        }
+        for (lang, description) in self.description {
+            *model.description_attr(lang) = Patch::Set(description);
+        }
+        model.hash = Some(model.hash());
+        Ok(model)
+    }
+
+    /// `item_id` must be non-empty without whitespace and, if `source_id` is given,
+    /// of the form `sourceId#itemId`.
+    fn validate_ids(&self) -> Result<(), ItemError> {
+        let invalid_id = |field, value: &String| ItemError::InvalidId {
+            field,
+            value: value.clone(),
+        };
+        if self.item_id.is_empty() || self.item_id.contains(char::is_whitespace) {
+            return Err(invalid_id("item_id", &self.item_id));
+        }
+        if let Some(source_id) = &self.source_id {
+            if source_id.is_empty() || source_id.contains(char::is_whitespace) {
+                return Err(invalid_id("source_id", source_id));
+            }
+            let is_in_source = self
+                .item_id
+                .strip_prefix(source_id.as_str())
+                .is_some_and(|rest| rest.len() > 1 && rest.starts_with('#'));
+            if !is_in_source {
+                return Err(invalid_id("item_id", &self.item_id));
+            }
         }
+        Ok(())
+    }
+}
+
+/// Machine-translated languages of `text`, or `None` if there are none.
+fn machine_translated_attr(text: &LocalizedText) -> Option<BTreeSet<Language>> {
+    let languages: BTreeSet<Language> = text.machine_translated().collect();
+    (!languages.is_empty()).then_some(languages)
+}
+
+impl ItemData {
+    /// The conversion as it was before [`ItemModel::try_from`] reported invalid data.
+    ///
+    /// Panics on any [`ItemError`].
+    #[deprecated(
+        note = "use `ItemModel::try_from`, which reports invalid data instead of panicking"
+    )]
+    pub fn into_model(self) -> ItemModel {
+        ItemModel::try_from(self).unwrap_or_else(|e| panic!("unconvertible item data: {e}"))
+    }
+}
+
+impl TryFrom<ItemData> for ItemModel {
+    type Error = ItemError;
+
+    fn try_from(data: ItemData) -> Result<Self, Self::Error> {
+        data.into_model_with(&SystemClock, &DEFAULT_EXCHANGE_RATES)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::clock::{Clock, FixedClock};
+    use crate::error::ItemError;
+    use crate::exchange_rate::{DEFAULT_EXCHANGE_RATES, ExchangeRateTable};
     use crate::item_data::ItemData;
     use crate::item_model::ItemModel;
     use crate::item_state::ItemState;
-    use crate::language::Language::{DE, EN};
-    use crate::price::Currency::EUR;
+    use crate::language::Language::{DE, EN, ES, FR};
+    use crate::localized_text::LocalizedText;
+    use crate::money::Money;
+    use crate::patch::Patch;
+    use crate::price::Currency::{EUR, USD};
     use crate::price::Price;
-    use std::collections::HashMap;
+    use crate::timestamp::{ParseTimestampError, Timestamp};
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+    use time::macros::datetime;
+
+    #[test]
+    fn should_normalize_price_with_rate_valid_at_created() {
+        let mut rates = ExchangeRateTable::new(EUR);
+        rates
+            .insert(USD, datetime!(2010-01-01 00:00 UTC), Money::new(125, 2))
+            .insert(USD, datetime!(2010-01-02 00:00 UTC), Money::new(2, 0));
+        let data = ItemData::new("https://foo.bar#123456".to_string())
+            .created("2010-01-01T12:00:00.001+01:00".parse().unwrap())
+            .price(Price::from_minor_units(USD, 1000))
+            .to_owned();
+
+        let actual = data.into_model_with_rates(&rates).unwrap();
+
+        assert_eq!(actual.price, Some(Money::new(800, 2)));
+    }
+
+    #[test]
+    fn should_stamp_missing_created_from_clock() {
+        let clock = FixedClock("2010-01-01T12:00:00.001Z".parse().unwrap());
+        let data = ItemData::new("https://foo.bar#123456".to_string());
+
+        let actual = data
+            .into_model_with(&clock, &DEFAULT_EXCHANGE_RATES)
+            .unwrap();
+
+        assert_eq!(actual.created, "2010-01-01T12:00:00.001Z".parse().ok());
+        assert_eq!(
+            actual.event_id,
+            Some("https://foo.bar#123456#2010-01-01T12:00:00.001Z".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case("", None)]
+    #[case("foo 123", None)]
+    #[case("https://foo.baz#123456", Some("https://foo.bar"))]
+    #[case("https://foo.bar#", Some("https://foo.bar"))]
+    #[case("https://foo.bar#123456", Some(""))]
+    fn should_reject_invalid_ids(#[case] item_id: &str, #[case] source_id: Option<&str>) {
+        let mut data = ItemData::new(item_id.to_string())
+            .created("2010-01-01T12:00:00.001Z".parse().unwrap())
+            .to_owned();
+        data.source_id = source_id.map(str::to_string);
+
+        let actual = ItemModel::try_from(data);
+
+        assert!(matches!(actual, Err(ItemError::InvalidId { .. })));
+    }
+
+    #[test]
+    fn should_reject_price_without_known_rate() {
+        let data = ItemData::new("https://foo.bar#123456".to_string())
+            .created("2010-01-01T12:00:00.001Z".parse().unwrap())
+            .price(Price::from_minor_units(USD, 1000))
+            .to_owned();
+
+        let actual = data.into_model_with_rates(&ExchangeRateTable::new(EUR));
+
+        assert_eq!(
+            actual,
+            Err(ItemError::CurrencyConversion {
+                currency: USD,
+                at: "2010-01-01T12:00:00.001Z".parse().unwrap(),
+            })
+        );
+    }
+
+    /// Reads an instant before year 0, as a misconfigured clock could.
+    struct OutOfRangeClock;
+
+    impl Clock for OutOfRangeClock {
+        fn now(&self) -> Timestamp {
+            self.try_now().expect("out of range")
+        }
+
+        fn try_now(&self) -> Result<Timestamp, ParseTimestampError> {
+            Timestamp::try_from(datetime!(-0001-01-01 00:00 UTC))
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_clock_reading() {
+        let clock = OutOfRangeClock;
+        let data = ItemData::new("https://foo.bar#123456".to_string());
+
+        let actual = data.into_model_with(&clock, &DEFAULT_EXCHANGE_RATES);
+
+        assert!(matches!(actual, Err(ItemError::Clock(_))));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn should_still_convert_infallibly() {
+        let data = ItemData::new("https://foo.bar#123456".to_string())
+            .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+            .to_owned();
+
+        let actual = data.clone().into_model();
+
+        assert_eq!(actual, ItemModel::try_from(data).unwrap());
+    }
+
+    #[test]
+    fn should_reject_malformed_raw_created() {
+        let mut data = ItemData::new("https://foo.bar#123456".to_string());
+
+        let actual = data.try_created("01.01.2010").map(|_| ());
+
+        assert_eq!(
+            actual,
+            Err(ItemError::InvalidTimestamp(ParseTimestampError::Malformed(
+                "01.01.2010".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn should_round_trip_all_languages_through_model() {
+        let data = ItemData::new("https://foo.bar#123456".to_string())
+            .created("2010-01-01T12:00:00.001+01:00".parse().unwrap())
+            .name(LocalizedText::from([
+                (EN, "bar".to_string()),
+                (DE, "balken".to_string()),
+                (FR, "barre".to_string()),
+                (ES, "barra".to_string()),
+            ]))
+            .description(LocalizedText::from([
+                (EN, "baz".to_string()),
+                (DE, "basis".to_string()),
+                (FR, "base".to_string()),
+                (ES, "base".to_string()),
+            ]))
+            .to_owned();
+
+        let model = ItemModel::try_from(data.clone()).unwrap();
+        let actual: ItemData = model.clone().into();
+
+        assert_eq!(model.name_fr, Patch::Set("barre".to_string()));
+        assert_eq!(model.description_es, Patch::Set("base".to_string()));
+        assert_eq!(actual, data);
+    }
+
+    fn data_with_provenance() -> ItemData {
+        let mut name = LocalizedText::original(DE, "balken".to_string());
+        name.insert_machine_translated(EN, "bar".to_string());
+        let mut description = LocalizedText::original(FR, "base".to_string());
+        description.insert_machine_translated(ES, "base".to_string());
+        ItemData::new("https://foo.bar#123456".to_string())
+            .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+            .name(name)
+            .description(description)
+            .to_owned()
+    }
+
+    #[test]
+    fn should_round_trip_provenance_through_model() {
+        let data = data_with_provenance();
+
+        let model = ItemModel::try_from(data.clone()).unwrap();
+        let stored = serde_json::to_string(&model).unwrap();
+        let actual: ItemData = serde_json::from_str::<ItemModel>(&stored).unwrap().into();
+
+        assert_eq!(model.name_original, Some(DE));
+        assert_eq!(
+            model.description_machine_translated,
+            Some(BTreeSet::from([ES]))
+        );
+        assert_eq!(actual, data);
+        assert_eq!(actual.name.original_language(), Some(DE));
+        assert!(actual.description.is_machine_translated(ES));
+    }
+
+    #[test]
+    fn should_serialize_provenance_next_to_language_map() {
+        let data = data_with_provenance();
+        let expected = r#"{"itemId":"https://foo.bar#123456","created":"2010-01-01T11:00:00.001Z","name":{"de":"balken","en":"bar"},"nameOriginal":"de","nameMachineTranslated":["en"],"description":{"fr":"base","es":"base"},"descriptionOriginal":"fr","descriptionMachineTranslated":["es"]}"#;
+
+        let actual = serde_json::to_string(&data).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(serde_json::from_str::<ItemData>(&actual).unwrap(), data);
+    }
 
     #[test]
     fn should_convert_data_into_model() {
         let data = ItemData {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T12:00:00.001+01:00".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(Price::new(EUR, 42f32)),
+            price: Some(Price::new(EUR, Money::from_major(42))),
             category: Some("foo".to_string()),
-            name: HashMap::from([(EN, "bar".to_string()), (DE, "balken".to_string())]),
-            description: HashMap::from([(EN, "baz".to_string()), (DE, "basis".to_string())]),
+            name: LocalizedText::from([(EN, "bar".to_string()), (DE, "balken".to_string())]),
+            description: LocalizedText::from([(EN, "baz".to_string()), (DE, "basis".to_string())]),
             url: Some("https://foo.bar?item=123456".to_string()),
             image_url: Some("https://foo.bar?item_img=123456".to_string()),
         };
         let expected = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::new(4200, 2)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "v2:934f27566445323950c101a3611ca8bec807d5af7722594a9be3b286163cee1c"
+                    .parse()
+                    .unwrap(),
             ),
         };
 
-        let actual: ItemModel = data.into();
+        let actual = ItemModel::try_from(data).unwrap();
 
         assert_eq!(actual, expected)
     }
@@ -0,0 +1,383 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// 32-byte BLAKE3 digest of an item's content, tagged with the version of the
+/// `HashSpec` it was made with.
+///
+/// Compares in constant time. Displays, parses and serializes as `v{version}:` and
+/// 64 lowercase hex digits, e.g. `v2:934f…`; version 1 digests stay untagged as they
+/// were first stored. [`base64url`] serializes alike, with 43 unpadded base64url
+/// characters instead of the hex digits.
+#[derive(Copy, Clone)]
+pub struct ItemDigest {
+    version: u16,
+    bytes: [u8; 32],
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParseDigestError {
+    InvalidLength { expected: usize, found: usize },
+    InvalidCharacter(char),
+    InvalidVersion(String),
+}
+
+impl fmt::Display for ParseDigestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDigestError::InvalidLength { expected, found } => {
+                write!(f, "digest must have {expected} characters, found {found}")
+            }
+            ParseDigestError::InvalidCharacter(c) => write!(f, "invalid digest character '{c}'"),
+            ParseDigestError::InvalidVersion(tag) => {
+                write!(f, "invalid digest version tag '{tag}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseDigestError {}
+
+impl ItemDigest {
+    /// Digest made with the spec of `version`, which must not be 0.
+    pub const fn new(version: u16, bytes: [u8; 32]) -> Self {
+        assert!(version > 0, "hash spec versions start at 1");
+        ItemDigest { version, bytes }
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    /// The 64 hex digits, without version tag.
+    pub fn to_hex(&self) -> String {
+        blake3::Hash::from(self.bytes).to_hex().to_string()
+    }
+
+    /// Parses 64 hex digits, in either case, as a digest of `version`.
+    pub fn from_hex(version: u16, s: &str) -> Result<Self, ParseDigestError> {
+        check_length(s, 64)?;
+        let mut bytes = [0u8; 32];
+        let mut digits = s.chars().map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(ParseDigestError::InvalidCharacter(c))
+        });
+        for byte in &mut bytes {
+            let (high, low) = (digits.next().unwrap()?, digits.next().unwrap()?);
+            *byte = high << 4 | low;
+        }
+        Ok(ItemDigest::new(version, bytes))
+    }
+
+    /// The 43 unpadded base64url characters, without version tag.
+    pub fn to_base64url(&self) -> String {
+        let mut encoded = String::with_capacity(43);
+        for chunk in self.bytes.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+            // 3 bytes make 4 characters, the trailing 2 bytes 3 characters
+            for i in 0..=chunk.len() {
+                encoded.push(BASE64URL[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            }
+        }
+        encoded
+    }
+
+    /// Parses 43 unpadded base64url characters as a digest of `version`.
+    pub fn from_base64url(version: u16, s: &str) -> Result<Self, ParseDigestError> {
+        check_length(s, 43)?;
+        let mut bits = 0u32;
+        let mut len = 0;
+        let mut bytes = [0u8; 32];
+        let mut i = 0;
+        for c in s.chars() {
+            let value = BASE64URL
+                .iter()
+                .position(|b| *b as char == c)
+                .ok_or(ParseDigestError::InvalidCharacter(c))?;
+            bits = bits << 6 | value as u32;
+            len += 6;
+            if len >= 8 {
+                len -= 8;
+                bytes[i] = (bits >> len) as u8;
+                i += 1;
+            }
+        }
+        // 43 characters carry 2 bits more than 32 bytes; they must be zero
+        if bits & 0b11 != 0 {
+            return Err(ParseDigestError::InvalidCharacter(
+                s.chars().last().unwrap(),
+            ));
+        }
+        Ok(ItemDigest::new(version, bytes))
+    }
+
+    fn tagged(&self, encoded: &str) -> String {
+        match self.version {
+            1 => encoded.to_string(),
+            version => format!("v{version}:{encoded}"),
+        }
+    }
+}
+
+/// Version and encoded digits of a possibly tagged digest; untagged ones are version 1.
+fn split_tag(s: &str) -> Result<(u16, &str), ParseDigestError> {
+    let Some((tag, encoded)) = s.split_once(':') else {
+        return Ok((1, s));
+    };
+    let version = tag
+        .strip_prefix('v')
+        .and_then(|version| version.parse().ok())
+        .filter(|version| *version > 0)
+        .ok_or_else(|| ParseDigestError::InvalidVersion(tag.to_string()))?;
+    Ok((version, encoded))
+}
+
+fn check_length(s: &str, expected: usize) -> Result<(), ParseDigestError> {
+    let found = s.chars().count();
+    if found != expected {
+        return Err(ParseDigestError::InvalidLength { expected, found });
+    }
+    Ok(())
+}
+
+impl From<blake3::Hash> for ItemDigest {
+    /// Version 1 digest, see [`ItemDigest::new`] for others.
+    fn from(hash: blake3::Hash) -> Self {
+        ItemDigest::new(1, *hash.as_bytes())
+    }
+}
+
+impl PartialEq for ItemDigest {
+    fn eq(&self, other: &Self) -> bool {
+        // constant-time
+        self.version == other.version
+            && blake3::Hash::from(self.bytes) == blake3::Hash::from(other.bytes)
+    }
+}
+
+impl Eq for ItemDigest {}
+
+impl Hash for ItemDigest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.version.hash(state);
+        self.bytes.hash(state);
+    }
+}
+
+impl fmt::Display for ItemDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.tagged(&self.to_hex()))
+    }
+}
+
+impl fmt::Debug for ItemDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ItemDigest({self})")
+    }
+}
+
+impl FromStr for ItemDigest {
+    type Err = ParseDigestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (version, hex) = split_tag(s)?;
+        ItemDigest::from_hex(version, hex)
+    }
+}
+
+impl Serialize for ItemDigest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ItemDigest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Base64url format, for `#[serde(with = "crate::item_digest::base64url")]`.
+pub mod base64url {
+    use crate::item_digest::{ItemDigest, split_tag};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(digest: &ItemDigest, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&digest.tagged(&digest.to_base64url()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ItemDigest, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        split_tag(&s)
+            .and_then(|(version, encoded)| ItemDigest::from_base64url(version, encoded))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::item_digest::{ItemDigest, ParseDigestError};
+    use rstest::rstest;
+    use serde::{Deserialize, Serialize};
+
+    const HEX: &str = "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b";
+    // as stored by the tagged string hashes of HashSpec::V2
+    const TAGGED: &str = "v2:934f27566445323950c101a3611ca8bec807d5af7722594a9be3b286163cee1c";
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Stored {
+        hex: ItemDigest,
+        #[serde(with = "crate::item_digest::base64url")]
+        base64url: ItemDigest,
+    }
+
+    #[test]
+    fn should_round_trip_hex() {
+        let digest: ItemDigest = HEX.parse().unwrap();
+
+        assert_eq!(digest.version(), 1);
+        assert_eq!(digest.to_string(), HEX);
+        assert_eq!(HEX.to_uppercase().parse::<ItemDigest>().unwrap(), digest);
+    }
+
+    #[test]
+    fn should_round_trip_version_tag() {
+        let digest: ItemDigest = TAGGED.parse().unwrap();
+
+        assert_eq!(digest.version(), 2);
+        assert_eq!(digest.to_hex(), &TAGGED[3..]);
+        assert_eq!(digest.to_string(), TAGGED);
+    }
+
+    #[test]
+    fn should_deserialize_tagged_string_hash() {
+        let actual = serde_json::from_str::<ItemDigest>(&format!(r#""{TAGGED}""#)).unwrap();
+
+        assert_eq!(actual, ItemDigest::from_hex(2, &TAGGED[3..]).unwrap());
+        assert_eq!(
+            serde_json::to_string(&actual).unwrap(),
+            format!(r#""{TAGGED}""#)
+        );
+    }
+
+    #[test]
+    fn should_tell_versions_apart() {
+        let v1 = ItemDigest::from_hex(1, HEX).unwrap();
+        let v2 = ItemDigest::from_hex(2, HEX).unwrap();
+
+        assert_ne!(v1, v2);
+        assert_eq!(v2.to_string(), format!("v2:{HEX}"));
+    }
+
+    #[test]
+    fn should_round_trip_base64url() {
+        let digest: ItemDigest = HEX.parse().unwrap();
+
+        let encoded = digest.to_base64url();
+
+        assert_eq!(encoded, "HRCmNDj_88zUh3whlcCjd6buDIyq2X5lKx5pxotFVXs");
+        assert_eq!(ItemDigest::from_base64url(1, &encoded), Ok(digest));
+    }
+
+    #[rstest]
+    #[case(&HEX[1..], ParseDigestError::InvalidLength { expected: 64, found: 63 })]
+    #[case(
+        "v2:0a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b",
+        ParseDigestError::InvalidLength { expected: 64, found: 61 }
+    )]
+    #[case(
+        "gd10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b",
+        ParseDigestError::InvalidCharacter('g')
+    )]
+    #[case(
+        "v0:1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b",
+        ParseDigestError::InvalidVersion("v0".to_string())
+    )]
+    #[case(
+        "x2:1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b",
+        ParseDigestError::InvalidVersion("x2".to_string())
+    )]
+    fn should_reject_invalid_hex(#[case] s: &str, #[case] expected: ParseDigestError) {
+        assert_eq!(s.parse::<ItemDigest>(), Err(expected));
+    }
+
+    #[rstest]
+    #[case("HRCmNDj_88zUh3whlcCjd6buDIyq2X5lKx5pxotFVX", ParseDigestError::InvalidLength { expected: 43, found: 42 })]
+    #[case(
+        "HRCmNDj/88zUh3whlcCjd6buDIyq2X5lKx5pxotFVXs",
+        ParseDigestError::InvalidCharacter('/')
+    )]
+    #[case(
+        "HRCmNDj_88zUh3whlcCjd6buDIyq2X5lKx5pxotFVXt",
+        ParseDigestError::InvalidCharacter('t')
+    )]
+    fn should_reject_invalid_base64url(#[case] s: &str, #[case] expected: ParseDigestError) {
+        assert_eq!(ItemDigest::from_base64url(1, s), Err(expected));
+    }
+
+    #[test]
+    fn should_serialize_hex_and_base64url() {
+        let digest: ItemDigest = HEX.parse().unwrap();
+        let stored = Stored {
+            hex: digest,
+            base64url: digest,
+        };
+        let expected = format!(
+            r#"{{"hex":"{HEX}","base64url":"HRCmNDj_88zUh3whlcCjd6buDIyq2X5lKx5pxotFVXs"}}"#
+        );
+
+        let actual = serde_json::to_string(&stored).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(serde_json::from_str::<Stored>(&actual).unwrap(), stored);
+    }
+
+    #[test]
+    fn should_serialize_base64url_with_version_tag() {
+        let digest: ItemDigest = TAGGED.parse().unwrap();
+        let stored = Stored {
+            hex: digest,
+            base64url: digest,
+        };
+
+        let actual = serde_json::to_string(&stored).unwrap();
+
+        assert!(actual.contains(r#""base64url":"v2:"#));
+        assert_eq!(serde_json::from_str::<Stored>(&actual).unwrap(), stored);
+    }
+
+    #[test]
+    fn should_reject_deserializing_invalid_digest() {
+        let actual = serde_json::from_str::<ItemDigest>(r#""abcdef""#).unwrap_err();
+
+        assert!(
+            actual
+                .to_string()
+                .contains("digest must have 64 characters, found 6")
+        );
+    }
+}
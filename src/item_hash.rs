@@ -1,27 +1,183 @@
+use crate::item_digest::ItemDigest;
 use crate::item_state::ItemState;
+use crate::language::Language;
+use crate::money::Money;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
 
 pub trait ItemHash {
-    fn hash(&self) -> String;
+    fn hash(&self) -> ItemDigest;
+}
+
+/// Item whose content can be hashed according to a [`HashSpec`]. Its [`ItemHash`]
+/// uses [`HashSpec::CURRENT`].
+pub trait HashContent {
+    fn hash_input(&self) -> HashInput<'_>;
+
+    fn hash_with(&self, spec: &HashSpec) -> ItemDigest {
+        spec.hash(&self.hash_input())
+    }
+}
+
+/// Attributes of an item that can participate in its hash.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum HashField {
+    State,
+    Price,
+    Category,
+    Name,
+    Description,
+    Url,
+    ImageUrl,
+}
+
+/// Content of an item as hashed, the same for `ItemData` and `ItemModel`.
+/// Texts are keyed by language, so their order never matters.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct HashInput<'a> {
+    pub state: Option<ItemState>,
+    // EUR
+    pub price: Option<Money>,
+    pub category: Option<&'a str>,
+    pub name: BTreeMap<Language, &'a str>,
+    pub description: BTreeMap<Language, &'a str>,
+    pub url: Option<&'a str>,
+    pub image_url: Option<&'a str>,
+}
+
+/// Which attributes participate in an item's hash, under a version. Hashes of
+/// different versions never match, even if they cover the same attributes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct HashSpec {
+    version: u16,
+    fields: &'static [HashField],
+}
+
+impl HashSpec {
+    /// State and EUR price, stored untagged as by [`hash_item_details`].
+    pub const V1: HashSpec = HashSpec {
+        version: 1,
+        fields: &[HashField::State, HashField::Price],
+    };
+
+    /// All attributes.
+    pub const V2: HashSpec = HashSpec::new(
+        2,
+        &[
+            HashField::State,
+            HashField::Price,
+            HashField::Category,
+            HashField::Name,
+            HashField::Description,
+            HashField::Url,
+            HashField::ImageUrl,
+        ],
+    );
+
+    pub const CURRENT: HashSpec = HashSpec::V2;
+
+    /// Spec hashing `fields`, in whatever order given, tagged with `version`. Version
+    /// 1 is taken by the untagged [`HashSpec::V1`].
+    pub const fn new(version: u16, fields: &'static [HashField]) -> Self {
+        assert!(version > 1, "hash spec version 1 is reserved");
+        HashSpec { version, fields }
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn includes(&self, field: HashField) -> bool {
+        self.fields.contains(&field)
+    }
+
+    /// Version of the spec a stored hash was made with; untagged ones are version 1.
+    pub fn version_of(hash: &str) -> Option<u16> {
+        match hash.split_once(':') {
+            Some((tag, _)) => tag.strip_prefix('v')?.parse().ok(),
+            None => Some(1),
+        }
+    }
+
+    pub fn hash(&self, input: &HashInput) -> ItemDigest {
+        if self.version == 1 {
+            return hash_item_details(input.state, input.price);
+        }
+        let hash = blake3::hash(self.encode(input).as_bytes());
+        ItemDigest::new(self.version, *hash.as_bytes())
+    }
+
+    /// Canonical encoding: included attributes in declaration order of [`HashField`],
+    /// each value length-prefixed, so no two inputs encode alike.
+    fn encode(&self, input: &HashInput) -> String {
+        let mut encoded = String::new();
+        if self.includes(HashField::State) {
+            let state = input.state.map(|state| state.to_string());
+            encode_value(&mut encoded, "state", state.as_deref());
+        }
+        if self.includes(HashField::Price) {
+            // normalized, so 42, 42.0 and 42.00 hash equally
+            let price = input.price.map(|price| price.normalized().to_string());
+            encode_value(&mut encoded, "price", price.as_deref());
+        }
+        if self.includes(HashField::Category) {
+            encode_value(&mut encoded, "category", input.category);
+        }
+        if self.includes(HashField::Name) {
+            encode_texts(&mut encoded, "name", &input.name);
+        }
+        if self.includes(HashField::Description) {
+            encode_texts(&mut encoded, "description", &input.description);
+        }
+        if self.includes(HashField::Url) {
+            encode_value(&mut encoded, "url", input.url);
+        }
+        if self.includes(HashField::ImageUrl) {
+            encode_value(&mut encoded, "image_url", input.image_url);
+        }
+        encoded
+    }
+}
+
+impl Default for HashSpec {
+    fn default() -> Self {
+        HashSpec::CURRENT
+    }
+}
+
+fn encode_value(buf: &mut String, name: &str, value: Option<&str>) {
+    let _ = match value {
+        Some(value) => write!(buf, "{name}={}:{value};", value.len()),
+        None => write!(buf, "{name}=-;"),
+    };
+}
+
+/// Texts sorted by language, so the order they were added in never matters.
+fn encode_texts(buf: &mut String, name: &str, texts: &BTreeMap<Language, &str>) {
+    let _ = write!(buf, "{name}={}:", texts.len());
+    for (language, text) in texts {
+        encode_value(buf, &language.to_string(), Some(text));
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ItemEventHash {
     #[serde(
         rename = "party_id",
-        serialize_with = "crate::ddb_prefix::ser_string_source_prefix",
-        deserialize_with = "crate::ddb_prefix::de_string_source_prefix"
+        serialize_with = "crate::ddb_prefix::ser_party_id_source_prefix",
+        deserialize_with = "crate::ddb_prefix::de_party_id_source_prefix"
     )]
     pub source_id: String,
 
     // sourceId#itemId#created
     #[serde(
-        serialize_with = "crate::ddb_prefix::ser_string_item_prefix",
-        deserialize_with = "crate::ddb_prefix::de_string_item_prefix"
+        serialize_with = "crate::ddb_prefix::ser_event_id_item_prefix",
+        deserialize_with = "crate::ddb_prefix::de_event_id_item_prefix"
     )]
     pub event_id: String,
 
-    pub hash: String,
+    pub hash: ItemDigest,
 }
 
 impl ItemEventHash {
@@ -41,33 +197,205 @@ impl ItemEventHash {
 }
 
 impl ItemHash for ItemEventHash {
-    fn hash(&self) -> String {
-        self.hash.clone()
+    fn hash(&self) -> ItemDigest {
+        self.hash
     }
 }
 
-pub fn hash_item_details(item_state: Option<ItemState>, eur_price: Option<f32>) -> String {
+pub fn hash_item_details(item_state: Option<ItemState>, eur_price: Option<Money>) -> ItemDigest {
     blake3::hash(
         format!(
             "{}|{}",
-            item_state.map(|x| x.to_string()).unwrap_or(String::new()),
-            eur_price.map(|x| x.to_string()).unwrap_or(String::new()),
+            item_state.map(|x| x.to_string()).unwrap_or_default(),
+            // normalized, so 42, 42.0 and 42.00 hash equally
+            eur_price
+                .map(|x| x.normalized().to_string())
+                .unwrap_or_default(),
         )
         .as_bytes(),
     )
-    .to_string()
+    .into()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::item_hash::ItemEventHash;
+    use crate::clock::FixedClock;
+    use crate::exchange_rate::ExchangeRateTable;
+    use crate::item_data::ItemData;
+    use crate::item_hash::{
+        HashContent, HashField, HashSpec, ItemEventHash, ItemHash, hash_item_details,
+    };
+    use crate::item_model::ItemModel;
+    use crate::item_state::ItemState;
+    use crate::language::Language::{DE, EN};
+    use crate::localized_text::LocalizedText;
+    use crate::money::Money;
+    use crate::price::Currency::{CHF, EUR, USD};
+    use crate::price::Price;
+    use rstest::rstest;
+    use time::macros::datetime;
+
+    const HEX: &str = "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b";
+
+    const NAMES_ONLY: HashSpec = HashSpec::new(3, &[HashField::Name, HashField::State]);
+    const NAMES_ONLY_REORDERED: HashSpec = HashSpec::new(3, &[HashField::State, HashField::Name]);
+
+    fn item_data() -> ItemData {
+        ItemData::new("foo#123456".to_string())
+            .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+            .source_id("foo".to_string())
+            .state(ItemState::AVAILABLE)
+            .price(Price::new(EUR, Money::new(4200, 2)))
+            .category("foo".to_string())
+            .name(LocalizedText::from([
+                (EN, "bar".to_string()),
+                (DE, "balken".to_string()),
+            ]))
+            .url("https://foo.bar?item=123456".to_string())
+            .to_owned()
+    }
+
+    #[rstest]
+    #[case(HashSpec::V1)]
+    #[case(HashSpec::V2)]
+    #[case(NAMES_ONLY)]
+    fn should_hash_data_and_model_alike(#[case] spec: HashSpec) {
+        let data = item_data();
+        let model = ItemModel::try_from(data.clone()).unwrap();
+
+        assert_eq!(data.hash_with(&spec), model.hash_with(&spec));
+    }
+
+    #[rstest]
+    #[case(Price::from_minor_units(USD, 1000))]
+    #[case(Price::from_minor_units(CHF, 1000))]
+    fn should_hash_data_and_model_alike_with_rate_table(#[case] price: Price) {
+        let mut rates = ExchangeRateTable::new(EUR);
+        rates
+            .insert(USD, datetime!(2010-01-01 00:00 UTC), Money::new(125, 2))
+            .insert(CHF, datetime!(2010-01-01 00:00 UTC), Money::new(16, 1));
+        let data = item_data().price(price).to_owned();
+        let clock = FixedClock("2010-01-02T00:00:00.000Z".parse().unwrap());
+
+        let model = data.clone().into_model_with(&clock, &rates).unwrap();
+
+        for spec in [HashSpec::V1, HashSpec::V2] {
+            let actual = data.hash_with_rates(&spec, &clock, &rates);
+            assert_eq!(actual, Ok(model.hash_with(&spec)));
+        }
+        assert_ne!(
+            data.hash_with_rates(&HashSpec::V2, &clock, &rates),
+            Ok(data.hash())
+        );
+    }
+
+    #[test]
+    fn should_hash_v1_untagged_like_item_details() {
+        let data = item_data();
+
+        assert_eq!(
+            data.hash_with(&HashSpec::V1),
+            hash_item_details(Some(ItemState::AVAILABLE), Some(Money::from_major(42)))
+        );
+    }
+
+    #[test]
+    fn should_tag_hash_with_version() {
+        let actual = item_data().hash();
+
+        assert_eq!(actual.version(), 2);
+        assert!(actual.to_string().starts_with("v2:"));
+        assert_eq!(actual.to_string().len(), 3 + 64);
+        assert_eq!(HashSpec::version_of(&actual.to_string()), Some(2));
+        assert_eq!(
+            HashSpec::version_of(&item_data().hash_with(&HashSpec::V1).to_string()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn should_separate_hashes_of_versions() {
+        const V3: HashSpec = HashSpec::new(
+            3,
+            &[
+                HashField::State,
+                HashField::Price,
+                HashField::Category,
+                HashField::Name,
+                HashField::Description,
+                HashField::Url,
+                HashField::ImageUrl,
+            ],
+        );
+        let data = item_data();
+
+        assert_ne!(data.hash_with(&HashSpec::V2), data.hash_with(&V3));
+        assert_eq!(data.hash(), data.hash_with(&HashSpec::V2));
+    }
+
+    #[test]
+    fn should_change_hash_when_any_field_changes() {
+        let data = item_data();
+        let renamed = item_data()
+            .name(LocalizedText::from([(EN, "baz".to_string())]))
+            .to_owned();
+        let moved = item_data()
+            .image_url("https://foo.bar?item_img=1".to_string())
+            .to_owned();
+
+        assert_ne!(data.hash(), renamed.hash());
+        assert_ne!(data.hash(), moved.hash());
+        assert_eq!(
+            data.hash_with(&HashSpec::V1),
+            renamed.hash_with(&HashSpec::V1)
+        );
+    }
+
+    #[test]
+    fn should_hash_texts_independent_of_order() {
+        let data = item_data();
+        let reordered = item_data()
+            .name(LocalizedText::from_iter([
+                (DE, "balken".to_string()),
+                (EN, "bar".to_string()),
+            ]))
+            .to_owned();
+
+        assert_eq!(data.hash(), reordered.hash());
+        assert_eq!(
+            data.hash_with(&NAMES_ONLY),
+            data.hash_with(&NAMES_ONLY_REORDERED)
+        );
+    }
+
+    #[test]
+    fn should_hash_only_fields_of_spec() {
+        let data = item_data();
+        let moved = item_data()
+            .url("https://foo.bar?item=1".to_string())
+            .to_owned();
+
+        assert_eq!(data.hash_with(&NAMES_ONLY), moved.hash_with(&NAMES_ONLY));
+    }
+
+    #[test]
+    fn should_not_confuse_values_across_fields() {
+        let mut data = item_data();
+        data.category = Some("foo;".to_string());
+        data.url = None;
+        let mut other = item_data();
+        other.category = Some("foo".to_string());
+        other.url = Some(";".to_string());
+
+        assert_ne!(data.hash(), other.hash());
+    }
 
     #[test]
     fn should_return_item_id_for_get_item_id() {
         let item_event_hash = ItemEventHash {
             source_id: "foo".to_string(),
             event_id: "foo#bar#2025-01-01T12:00:00.001+01:00".to_string(),
-            hash: "123465".to_string(),
+            hash: HEX.parse().unwrap(),
         };
 
         let expected = "foo#bar";
@@ -81,23 +409,23 @@ mod tests {
         let item = ItemEventHash {
             source_id: "foo".to_string(),
             event_id: "foo#bar#123456".to_string(),
-            hash: "abcdef".to_string(),
+            hash: HEX.parse().unwrap(),
         };
         let actual = serde_json::to_string(&item).unwrap();
 
-        let expected = r#"{"party_id":"source#foo","event_id":"item#foo#bar#123456","hash":"abcdef"}"#;
+        let expected = r#"{"party_id":"source#foo","event_id":"item#foo#bar#123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn should_deserialize() {
-        let item_json = r#"{"party_id":"source#foo","event_id":"item#foo#bar#123456","hash":"abcdef"}"#;
+        let item_json = r#"{"party_id":"source#foo","event_id":"item#foo#bar#123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
         let actual = serde_json::from_str::<ItemEventHash>(item_json).unwrap();
 
         let expected = ItemEventHash {
             source_id: "foo".to_string(),
             event_id: "foo#bar#123456".to_string(),
-            hash: "abcdef".to_string(),
+            hash: HEX.parse().unwrap(),
         };
         assert_eq!(expected, actual);
     }
@@ -107,7 +435,7 @@ mod tests {
         let item = ItemEventHash {
             source_id: "foo".to_string(),
             event_id: "foo#bar#123456".to_string(),
-            hash: "abcdef".to_string(),
+            hash: HEX.parse().unwrap(),
         };
         let serialized = serde_json::to_string(&item).unwrap();
         let actual: ItemEventHash = serde_json::from_str(&serialized).unwrap();
@@ -117,7 +445,7 @@ mod tests {
 
     #[test]
     fn should_round_trip_deserialize_eq_serialize() {
-        let item_json = r#"{"party_id":"source#foo","event_id":"item#foo#bar#123456","hash":"abcdef"}"#;
+        let item_json = r#"{"party_id":"source#foo","event_id":"item#foo#bar#123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
         let deserialized = serde_json::from_str::<ItemEventHash>(item_json).unwrap();
 
         let actual = serde_json::to_string(&deserialized).unwrap();
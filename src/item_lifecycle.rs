@@ -0,0 +1,213 @@
+use crate::error::Error;
+use crate::item_model::ItemModel;
+use crate::item_state::ItemState;
+use time::{Duration, OffsetDateTime};
+
+/// Period an item spent in one state; `left_at` is `None` while it still is in it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct StateSegment {
+    pub state: ItemState,
+    pub entered_at: OffsetDateTime,
+    pub left_at: Option<OffsetDateTime>,
+}
+
+impl StateSegment {
+    /// Time spent in the segment, counting an open segment up to `now`.
+    pub fn duration(&self, now: OffsetDateTime) -> Duration {
+        self.left_at.unwrap_or(now) - self.entered_at
+    }
+}
+
+/// States an item went through, oldest first, projected from its item-events.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Lifecycle {
+    segments: Vec<StateSegment>,
+}
+
+impl Lifecycle {
+    pub fn segments(&self) -> &[StateSegment] {
+        &self.segments
+    }
+
+    pub fn current_state(&self) -> Option<ItemState> {
+        self.segments.last().map(|segment| segment.state)
+    }
+
+    /// When the item first entered `state`.
+    pub fn entered_at(&self, state: ItemState) -> Option<OffsetDateTime> {
+        self.segments
+            .iter()
+            .find(|segment| segment.state == state)
+            .map(|segment| segment.entered_at)
+    }
+
+    /// Total time spent in `state`, counting a still open segment up to `now`.
+    pub fn time_in(&self, state: ItemState, now: OffsetDateTime) -> Duration {
+        self.segments
+            .iter()
+            .filter(|segment| segment.state == state)
+            .map(|segment| segment.duration(now))
+            .sum()
+    }
+
+    /// Time from the first recorded state until the item was sold.
+    pub fn time_to_sale(&self) -> Option<Duration> {
+        let first = self.segments.first()?;
+        Some(self.entered_at(ItemState::SOLD)? - first.entered_at)
+    }
+
+    /// Total time the item was reserved, counting a pending reservation up to `now`.
+    pub fn reserved_duration(&self, now: OffsetDateTime) -> Duration {
+        self.time_in(ItemState::RESERVED, now)
+    }
+}
+
+impl TryFrom<&[ItemModel]> for Lifecycle {
+    type Error = Error;
+
+    /// Project item-events - in any order - to state segments, using `created` as the
+    /// instant of each event. Events without state and repeated states are skipped.
+    fn try_from(item_events: &[ItemModel]) -> Result<Self, Self::Error> {
+        let mut changes = item_events
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| event.state.map(|state| (index, event, state)))
+            .map(|(index, event, state)| {
+                let created = event.created.ok_or_else(|| Error::MissingCreated {
+                    index,
+                    event_id: event.event_id.clone(),
+                })?;
+                Ok((created.to_offset_date_time(), state))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        changes.sort_by_key(|(instant, _)| *instant);
+
+        let mut segments: Vec<StateSegment> = Vec::new();
+        for (instant, state) in changes {
+            if let Some(current) = segments.last_mut() {
+                if current.state == state {
+                    continue;
+                }
+                current.left_at = Some(instant);
+            }
+            segments.push(StateSegment {
+                state,
+                entered_at: instant,
+                left_at: None,
+            });
+        }
+        Ok(Lifecycle { segments })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::item_lifecycle::{Lifecycle, StateSegment};
+    use crate::item_model::ItemModel;
+    use crate::item_state::ItemState::{AVAILABLE, LISTED, RESERVED, SOLD};
+    use crate::test_support::event;
+    use time::Duration;
+    use time::macros::datetime;
+
+    fn item_events() -> Vec<ItemModel> {
+        vec![
+            event("2010-01-10T12:00:00.000+01:00")
+                .state(SOLD)
+                .to_owned(),
+            event("2010-01-07T12:00:00.000+01:00")
+                .state(RESERVED)
+                .to_owned(),
+            event("2010-01-06T12:00:00.000+01:00")
+                .state(AVAILABLE)
+                .to_owned(),
+            event("2010-01-04T12:00:00.000+01:00")
+                .state(RESERVED)
+                .to_owned(),
+            event("2010-01-03T12:00:00.000+01:00"),
+            event("2010-01-02T12:00:00.000+01:00")
+                .state(AVAILABLE)
+                .to_owned(),
+            event("2010-01-01T18:00:00.000+01:00")
+                .state(LISTED)
+                .to_owned(),
+            event("2010-01-01T12:00:00.000+01:00")
+                .state(LISTED)
+                .to_owned(),
+        ]
+    }
+
+    #[test]
+    fn should_project_state_segments() {
+        let actual = Lifecycle::try_from(&item_events()[..]).unwrap();
+
+        assert_eq!(actual.segments().len(), 6);
+        assert_eq!(
+            actual.segments()[0],
+            StateSegment {
+                state: LISTED,
+                entered_at: datetime!(2010-01-01 12:00 +01:00),
+                left_at: Some(datetime!(2010-01-02 12:00 +01:00)),
+            }
+        );
+        assert_eq!(
+            actual.segments()[5],
+            StateSegment {
+                state: SOLD,
+                entered_at: datetime!(2010-01-10 12:00 +01:00),
+                left_at: None,
+            }
+        );
+        assert_eq!(actual.current_state(), Some(SOLD));
+    }
+
+    #[test]
+    fn should_compute_time_to_sale_and_reserved_duration() {
+        let actual = Lifecycle::try_from(&item_events()[..]).unwrap();
+
+        assert_eq!(actual.time_to_sale(), Some(Duration::days(9)));
+        assert_eq!(
+            actual.reserved_duration(datetime!(2010-02-01 00:00 UTC)),
+            Duration::days(5)
+        );
+    }
+
+    #[test]
+    fn should_count_pending_reservation_up_to_now() {
+        let item_events = [
+            event("2010-01-02T12:00:00.000Z").state(RESERVED).to_owned(),
+            event("2010-01-01T12:00:00.000Z")
+                .state(AVAILABLE)
+                .to_owned(),
+        ];
+
+        let actual = Lifecycle::try_from(&item_events[..]).unwrap();
+
+        assert_eq!(actual.time_to_sale(), None);
+        assert_eq!(
+            actual.reserved_duration(datetime!(2010-01-03 00:00 UTC)),
+            Duration::hours(12)
+        );
+    }
+
+    #[test]
+    fn should_reject_state_change_without_created() {
+        let mut item_event = event("2010-01-01T12:00:00.000Z").state(SOLD).to_owned();
+        item_event.created = None;
+
+        let item_events = [
+            event("2010-01-02T12:00:00.000Z").state(LISTED).to_owned(),
+            item_event,
+        ];
+
+        let actual = Lifecycle::try_from(&item_events[..]);
+
+        assert_eq!(
+            actual,
+            Err(Error::MissingCreated {
+                index: 1,
+                event_id: Some("foo#123456#2010-01-01T12:00:00.000Z".to_string()),
+            })
+        );
+    }
+}
@@ -1,37 +1,45 @@
+use crate::clock::{Clock, SystemClock};
+use crate::error::Error;
 use crate::item_data::ItemData;
-use crate::item_hash::{ItemHash, hash_item_details};
+use crate::item_digest::ItemDigest;
+use crate::item_hash::{HashContent, HashInput, HashSpec, ItemHash};
 use crate::item_state::ItemState;
-use crate::language::I18nString;
-use crate::language::Language::{DE, EN};
+use crate::language::Language;
+use crate::language::Language::{DE, EN, ES, FR};
+use crate::localized_text::LocalizedText;
+use crate::money::Money;
+use crate::patch::Patch;
 use crate::price::Currency::EUR;
 use crate::price::Price;
+use crate::timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeSet;
+use strum::IntoEnumIterator;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct ItemModel {
     // sourceId#itemId
     #[serde(
         rename = "pk",
-        serialize_with = "crate::ddb_prefix::ser_string_item_prefix",
-        deserialize_with = "crate::ddb_prefix::de_string_item_prefix"
+        serialize_with = "crate::ddb_prefix::ser_pk_item_prefix",
+        deserialize_with = "crate::ddb_prefix::de_pk_item_prefix"
     )]
     pub item_id: String,
 
-    // ISO 8601: 2010-01-01T12:00:00.001+01:00
+    // RFC 3339 in UTC: 2010-01-01T11:00:00.001Z
     #[serde(
         rename = "sk",
-        serialize_with = "crate::ddb_prefix::ser_opt_string_item_prefix",
-        deserialize_with = "crate::ddb_prefix::de_opt_string_item_prefix",
+        serialize_with = "crate::ddb_prefix::ser_opt_sk_item_prefix",
+        deserialize_with = "crate::ddb_prefix::de_opt_sk_item_prefix",
         skip_serializing_if = "Option::is_none",
         default
     )]
-    pub created: Option<String>,
+    pub created: Option<Timestamp>,
 
     #[serde(
         rename = "party_id",
-        serialize_with = "crate::ddb_prefix::ser_opt_string_source_prefix",
-        deserialize_with = "crate::ddb_prefix::de_opt_string_source_prefix",
+        serialize_with = "crate::ddb_prefix::ser_opt_party_id_source_prefix",
+        deserialize_with = "crate::ddb_prefix::de_opt_party_id_source_prefix",
         skip_serializing_if = "Option::is_none",
         default
     )]
@@ -39,47 +47,73 @@ pub struct ItemModel {
 
     // sourceId#itemId#created
     #[serde(
-        serialize_with = "crate::ddb_prefix::ser_opt_string_item_prefix",
-        deserialize_with = "crate::ddb_prefix::de_opt_string_item_prefix",
+        serialize_with = "crate::ddb_prefix::ser_opt_event_id_item_prefix",
+        deserialize_with = "crate::ddb_prefix::de_opt_event_id_item_prefix",
         skip_serializing_if = "Option::is_none",
         default
     )]
     pub event_id: Option<String>,
 
     #[serde(
-        serialize_with = "crate::ddb_prefix::ser_opt_item_state_item_prefix",
-        deserialize_with = "crate::ddb_prefix::de_opt_item_state_item_prefix",
+        serialize_with = "crate::ddb_prefix::ser_opt_state_item_prefix",
+        deserialize_with = "crate::ddb_prefix::de_opt_state_item_prefix",
         skip_serializing_if = "Option::is_none",
         default
     )]
     pub state: Option<ItemState>,
 
+    // EUR
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub price: Option<f32>,
+    pub price: Option<Money>,
 
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub category: Patch<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub name_en: Option<String>,
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub name_en: Patch<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub description_en: Option<String>,
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub description_en: Patch<String>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub name_de: Patch<String>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub description_de: Patch<String>,
 
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub name_fr: Patch<String>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub description_fr: Patch<String>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub name_es: Patch<String>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub description_es: Patch<String>,
+
+    // language of the original name, if known
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub name_de: Option<String>,
+    pub name_original: Option<Language>,
 
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub description_de: Option<String>,
+    pub name_machine_translated: Option<BTreeSet<Language>>,
 
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub url: Option<String>,
+    pub description_original: Option<Language>,
 
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub image_url: Option<String>,
+    pub description_machine_translated: Option<BTreeSet<Language>>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub url: Patch<String>,
+
+    #[serde(skip_serializing_if = "Patch::is_unchanged", default)]
+    pub image_url: Patch<String>,
 
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub hash: Option<String>,
+    pub hash: Option<ItemDigest>,
 }
 
 impl ItemModel {
@@ -91,13 +125,21 @@ impl ItemModel {
             event_id: None,
             state: None,
             price: None,
-            category: None,
-            name_en: None,
-            description_en: None,
-            name_de: None,
-            description_de: None,
-            url: None,
-            image_url: None,
+            category: Patch::Unchanged,
+            name_en: Patch::Unchanged,
+            description_en: Patch::Unchanged,
+            name_de: Patch::Unchanged,
+            description_de: Patch::Unchanged,
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Unchanged,
+            image_url: Patch::Unchanged,
             hash: None,
         }
     }
@@ -109,7 +151,7 @@ impl ItemModel {
         self
     }
 
-    pub fn created(&mut self, created: String) -> &mut Self {
+    pub fn created(&mut self, created: Timestamp) -> &mut Self {
         self.created = Some(created);
         self
     }
@@ -124,154 +166,467 @@ impl ItemModel {
         self
     }
 
-    pub fn price(&mut self, price: f32) -> &mut Self {
+    pub fn price(&mut self, price: Money) -> &mut Self {
         self.price = Some(price);
         self
     }
 
     pub fn category(&mut self, category: String) -> &mut Self {
-        self.category = Some(category);
+        self.category = Patch::Set(category);
         self
     }
 
     pub fn name_en(&mut self, name_en: String) -> &mut Self {
-        self.name_en = Some(name_en);
+        self.name_en = Patch::Set(name_en);
         self
     }
 
     pub fn description_en(&mut self, description_en: String) -> &mut Self {
-        self.description_en = Some(description_en);
+        self.description_en = Patch::Set(description_en);
+        self
+    }
+
+    pub fn name_de(&mut self, name_de: String) -> &mut Self {
+        self.name_de = Patch::Set(name_de);
+        self
+    }
+
+    pub fn description_de(&mut self, description_de: String) -> &mut Self {
+        self.description_de = Patch::Set(description_de);
+        self
+    }
+
+    pub fn name_fr(&mut self, name_fr: String) -> &mut Self {
+        self.name_fr = Patch::Set(name_fr);
         self
     }
 
-    pub fn name_de(&mut self, name_en: String) -> &mut Self {
-        self.name_de = Some(name_en);
+    pub fn description_fr(&mut self, description_fr: String) -> &mut Self {
+        self.description_fr = Patch::Set(description_fr);
         self
     }
 
-    pub fn description_de(&mut self, description_en: String) -> &mut Self {
-        self.description_de = Some(description_en);
+    pub fn name_es(&mut self, name_es: String) -> &mut Self {
+        self.name_es = Patch::Set(name_es);
+        self
+    }
+
+    pub fn description_es(&mut self, description_es: String) -> &mut Self {
+        self.description_es = Patch::Set(description_es);
         self
     }
 
     pub fn url(&mut self, url: String) -> &mut Self {
-        self.url = Some(url);
+        self.url = Patch::Set(url);
         self
     }
 
     pub fn image_url(&mut self, image_url: String) -> &mut Self {
-        self.image_url = Some(image_url);
+        self.image_url = Patch::Set(image_url);
         self
     }
 
     // endregion
+
+    // region per-language attributes
+
+    /// Attribute holding the name in `lang`. Every `Language` must map to its own
+    /// attribute, so conversions from and to `ItemData` stay lossless.
+    pub fn name_attr(&mut self, lang: Language) -> &mut Patch<String> {
+        match lang {
+            EN => &mut self.name_en,
+            DE => &mut self.name_de,
+            FR => &mut self.name_fr,
+            ES => &mut self.name_es,
+        }
+    }
+
+    /// Attribute holding the description in `lang`, see [`ItemModel::name_attr`].
+    pub fn description_attr(&mut self, lang: Language) -> &mut Patch<String> {
+        match lang {
+            EN => &mut self.description_en,
+            DE => &mut self.description_de,
+            FR => &mut self.description_fr,
+            ES => &mut self.description_es,
+        }
+    }
+
+    pub fn name_lang(&self, lang: Language) -> Option<&String> {
+        match lang {
+            EN => self.name_en.value(),
+            DE => self.name_de.value(),
+            FR => self.name_fr.value(),
+            ES => self.name_es.value(),
+        }
+    }
+
+    pub fn description_lang(&self, lang: Language) -> Option<&String> {
+        match lang {
+            EN => self.description_en.value(),
+            DE => self.description_de.value(),
+            FR => self.description_fr.value(),
+            ES => self.description_es.value(),
+        }
+    }
+
+    // endregion
+}
+
+impl HashContent for ItemModel {
+    fn hash_input(&self) -> HashInput<'_> {
+        HashInput {
+            state: self.state,
+            price: self.price,
+            category: self.category.value().map(String::as_str),
+            name: Language::iter()
+                .filter_map(|lang| Some((lang, self.name_lang(lang)?.as_str())))
+                .collect(),
+            description: Language::iter()
+                .filter_map(|lang| Some((lang, self.description_lang(lang)?.as_str())))
+                .collect(),
+            url: self.url.value().map(String::as_str),
+            image_url: self.image_url.value().map(String::as_str),
+        }
+    }
 }
 
 impl ItemHash for ItemModel {
-    fn hash(&self) -> String {
-        hash_item_details(self.state, self.price)
+    fn hash(&self) -> ItemDigest {
+        self.hash_with(&HashSpec::CURRENT)
     }
 }
 
-impl Into<ItemData> for ItemModel {
-    fn into(self) -> ItemData {
+impl From<ItemModel> for ItemData {
+    fn from(mut model: ItemModel) -> Self {
+        let mut name = LocalizedText::new();
+        let mut description = LocalizedText::new();
+        for lang in Language::iter() {
+            if let Some(name_lang) = model.name_attr(lang).take().into_value() {
+                name.insert(lang, name_lang);
+            }
+            if let Some(description_lang) = model.description_attr(lang).take().into_value() {
+                description.insert(lang, description_lang);
+            }
+        }
+        for lang in model.name_machine_translated.into_iter().flatten() {
+            name.set_machine_translated(lang);
+        }
+        if let Some(original) = model.name_original {
+            name.set_original_language(original);
+        }
+        for lang in model.description_machine_translated.into_iter().flatten() {
+            description.set_machine_translated(lang);
+        }
+        if let Some(original) = model.description_original {
+            description.set_original_language(original);
+        }
         ItemData {
-            item_id: self.item_id,
-            created: self.created,
-            source_id: self.source_id,
-            state: self.state,
-            price: self.price.map(|price| Price::new(EUR, price)),
-            category: self.category,
-            name: {
-                let mut name: I18nString = HashMap::new();
-                if self.name_en.is_some() {
-                    name.insert(EN, self.name_en.unwrap());
-                }
-                if self.name_de.is_some() {
-                    name.insert(DE, self.name_de.unwrap());
-                }
-                name
-            },
-            description: {
-                let mut description: I18nString = HashMap::new();
-                if self.description_en.is_some() {
-                    description.insert(EN, self.description_en.unwrap());
-                }
-                if self.description_de.is_some() {
-                    description.insert(DE, self.description_de.unwrap());
-                }
-                description
-            },
-            url: self.url,
-            image_url: self.image_url,
+            item_id: model.item_id,
+            created: model.created,
+            source_id: model.source_id,
+            state: model.state,
+            price: model.price.map(|price| Price::new(EUR, price)),
+            category: model.category.into_value(),
+            name,
+            description,
+            url: model.url.into_value(),
+            image_url: model.image_url.into_value(),
         }
     }
 }
 
-impl TryFrom<&[ItemModel]> for ItemModel {
-    type Error = String;
+/// How [`ItemModel::materialize`] treats the order of item-events.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Materialization {
+    /// Item-events must be sorted by latest `created` (first), each having one.
+    #[default]
+    Strict,
+    /// Item-events are sorted by latest `created` (first) before merging; events
+    /// without `created` count as the oldest.
+    Lenient,
+}
 
-    /// Convert item-events - sorted by latest (first) - to materialized item.
-    fn try_from(item_events: &[ItemModel]) -> Result<Self, Self::Error> {
+impl ItemModel {
+    /// Merge one item's item-events into the materialized item, newer attributes
+    /// taking precedence.
+    pub fn materialize(item_events: &[ItemModel], mode: Materialization) -> Result<Self, Error> {
         if item_events.is_empty() {
-            Err("Given 'item_events' were empty.".to_string())
-        } else {
-            let mut item_id = None;
-            let mut source_id = None;
-            let mut created = None;
-            let mut event_id = None;
-            let mut state = None;
-            let mut price = None;
-            let mut category = None;
-            let mut name_en = None;
-            let mut description_en = None;
-            let mut name_de = None;
-            let mut description_de = None;
-            let mut url = None;
-            let mut image_url = None;
-            let mut hash = None;
-
-            for event_ref in item_events {
-                let event = event_ref.to_owned();
-                item_id = item_id.or(Some(event.item_id));
-                source_id = source_id.or(event.source_id);
-                created = created.or(event.created);
-                event_id = event_id.or(event.event_id);
-                state = state.or(event.state);
-                price = price.or(event.price);
-                category = category.or(event.category);
-                name_en = name_en.or(event.name_en);
-                description_en = description_en.or(event.description_en);
-                name_de = name_de.or(event.name_de);
-                description_de = description_de.or(event.description_de);
-                url = url.or(event.url);
-                image_url = image_url.or(event.image_url);
-                hash = hash.or(event.hash);
+            return Err(Error::EmptyEvents);
+        }
+        check_item_ids(item_events)?;
+        match mode {
+            Materialization::Strict => {
+                check_newest_first(item_events)?;
+                Ok(merge_newest_first(item_events))
+            }
+            Materialization::Lenient => {
+                let mut sorted = item_events.to_vec();
+                sorted.sort_by_key(|event| std::cmp::Reverse(event.created));
+                Ok(merge_newest_first(&sorted))
             }
+        }
+    }
+}
+
+impl ItemModel {
+    /// The item as it was at `instant`: item-events - sorted by latest (first), see
+    /// [`Materialization::Strict`] - created after it are ignored. `None` if the item
+    /// had no item-event yet.
+    pub fn materialize_as_of(
+        item_events: &[ItemModel],
+        instant: Timestamp,
+    ) -> Result<Option<Self>, Error> {
+        check_strict(item_events)?;
+        let covered = &item_events[created_after(item_events, instant)..];
+        Ok((!covered.is_empty()).then(|| merge_newest_first(covered)))
+    }
+
+    /// Every state of the item between `from` and `to`, oldest first: the state as
+    /// of `from`, if any, followed by the one after each item-event created later
+    /// but not after `to`.
+    pub fn materialize_between(
+        item_events: &[ItemModel],
+        from: Timestamp,
+        to: Timestamp,
+    ) -> Result<Vec<Self>, Error> {
+        check_strict(item_events)?;
+        if from > to {
+            return Ok(Vec::new());
+        }
+        let oldest = created_after(item_events, from);
+        let newest = created_after(item_events, to);
+        // unresolved, so cleared attributes keep shadowing older events
+        let mut current = item_events[oldest..]
+            .iter()
+            .cloned()
+            .reduce(ItemModel::merged_over);
+        let mut states: Vec<ItemModel> = current.iter().cloned().map(ItemModel::resolved).collect();
+        for event in item_events[newest..oldest].iter().rev() {
+            let next = match current {
+                Some(older) => event.clone().merged_over(older),
+                None => event.clone(),
+            };
+            states.push(next.clone().resolved());
+            current = Some(next);
+        }
+        Ok(states)
+    }
+}
 
-            Ok(ItemModel {
-                item_id: item_id.unwrap(),
-                source_id,
+fn check_strict(item_events: &[ItemModel]) -> Result<(), Error> {
+    if item_events.is_empty() {
+        return Err(Error::EmptyEvents);
+    }
+    check_item_ids(item_events)?;
+    check_newest_first(item_events)
+}
+
+/// Number of leading item-events - sorted by latest (first) - created after `instant`.
+fn created_after(item_events: &[ItemModel], instant: Timestamp) -> usize {
+    item_events.partition_point(|event| event.created > Some(instant))
+}
+
+fn check_item_ids(item_events: &[ItemModel]) -> Result<(), Error> {
+    let expected = &item_events[0].item_id;
+    match item_events
+        .iter()
+        .enumerate()
+        .find(|(_, event)| event.item_id != *expected)
+    {
+        Some((index, event)) => Err(Error::MismatchedItemIds {
+            index,
+            expected: expected.clone(),
+            found: event.item_id.clone(),
+            event_id: event.event_id.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn check_newest_first(item_events: &[ItemModel]) -> Result<(), Error> {
+    let mut previous: Option<Timestamp> = None;
+    for (index, event) in item_events.iter().enumerate() {
+        let Some(created) = event.created else {
+            return Err(Error::MissingCreated {
+                index,
+                event_id: event.event_id.clone(),
+            });
+        };
+        if let Some(previous) = previous
+            && created > previous
+        {
+            return Err(Error::UnsortedEvents {
+                index,
+                event_id: event.event_id.clone(),
                 created,
-                event_id,
-                state,
-                price,
-                category,
-                name_en,
-                description_en,
-                name_de,
-                description_de,
-                url,
-                image_url,
-                hash,
-            })
+                previous,
+            });
+        }
+        previous = Some(created);
+    }
+    Ok(())
+}
+
+/// Merge non-empty item-events of one item - sorted by latest (first). The newest
+/// event that sets or clears an attribute decides it.
+fn merge_newest_first(item_events: &[ItemModel]) -> ItemModel {
+    item_events[1..]
+        .iter()
+        .fold(item_events[0].clone(), |newer, older| {
+            newer.merged_over(older.clone())
+        })
+        .resolved()
+}
+
+impl ItemModel {
+    /// Combine with an `older` event of the same item: attributes this event leaves
+    /// unset or unchanged are taken from `older`. Cleared attributes stay cleared
+    /// until [`ItemModel::resolved`].
+    pub fn merged_over(self, older: ItemModel) -> ItemModel {
+        ItemModel {
+            item_id: self.item_id,
+            source_id: self.source_id.or(older.source_id),
+            created: self.created.or(older.created),
+            event_id: self.event_id.or(older.event_id),
+            state: self.state.or(older.state),
+            price: self.price.or(older.price),
+            category: self.category.or(older.category),
+            name_en: self.name_en.or(older.name_en),
+            description_en: self.description_en.or(older.description_en),
+            name_de: self.name_de.or(older.name_de),
+            description_de: self.description_de.or(older.description_de),
+            name_fr: self.name_fr.or(older.name_fr),
+            description_fr: self.description_fr.or(older.description_fr),
+            name_es: self.name_es.or(older.name_es),
+            description_es: self.description_es.or(older.description_es),
+            name_original: self.name_original.or(older.name_original),
+            name_machine_translated: self
+                .name_machine_translated
+                .or(older.name_machine_translated),
+            description_original: self.description_original.or(older.description_original),
+            description_machine_translated: self
+                .description_machine_translated
+                .or(older.description_machine_translated),
+            url: self.url.or(older.url),
+            image_url: self.image_url.or(older.image_url),
+            hash: self.hash.or(older.hash),
+        }
+    }
+
+    /// The item as materialized: cleared attributes become absent.
+    pub fn resolved(self) -> ItemModel {
+        ItemModel {
+            category: self.category.resolved(),
+            name_en: self.name_en.resolved(),
+            description_en: self.description_en.resolved(),
+            name_de: self.name_de.resolved(),
+            description_de: self.description_de.resolved(),
+            name_fr: self.name_fr.resolved(),
+            description_fr: self.description_fr.resolved(),
+            name_es: self.name_es.resolved(),
+            description_es: self.description_es.resolved(),
+            url: self.url.resolved(),
+            image_url: self.image_url.resolved(),
+            ..self
+        }
+    }
+
+    /// Sparse item-event turning the `materialized` item into the `incoming` one, a
+    /// full item-event as scraped: only changed attributes are set, and attributes
+    /// the scrape no longer has are cleared. `None` if nothing changed.
+    ///
+    /// `state` and `price` cannot be cleared, so a scrape lacking them keeps the
+    /// materialized ones. `created`, `event_id` and `source_id` are taken from
+    /// `incoming`, the `hash` is that of the item after applying the event. A missing
+    /// `created` is taken from the system clock, see [`ItemModel::diff_with`].
+    pub fn diff(materialized: &ItemModel, incoming: &ItemModel) -> Option<ItemModel> {
+        ItemModel::diff_with(materialized, incoming, &SystemClock)
+    }
+
+    /// Like [`ItemModel::diff`], but takes a missing `created` from `clock`.
+    pub fn diff_with(
+        materialized: &ItemModel,
+        incoming: &ItemModel,
+        clock: &dyn Clock,
+    ) -> Option<ItemModel> {
+        let event = ItemModel {
+            state: changed_value(materialized.state, incoming.state),
+            price: changed_value(materialized.price, incoming.price),
+            category: changed_attr(&materialized.category, &incoming.category),
+            name_en: changed_attr(&materialized.name_en, &incoming.name_en),
+            description_en: changed_attr(&materialized.description_en, &incoming.description_en),
+            name_de: changed_attr(&materialized.name_de, &incoming.name_de),
+            description_de: changed_attr(&materialized.description_de, &incoming.description_de),
+            name_fr: changed_attr(&materialized.name_fr, &incoming.name_fr),
+            description_fr: changed_attr(&materialized.description_fr, &incoming.description_fr),
+            name_es: changed_attr(&materialized.name_es, &incoming.name_es),
+            description_es: changed_attr(&materialized.description_es, &incoming.description_es),
+            name_original: changed_value(materialized.name_original, incoming.name_original),
+            name_machine_translated: changed_value(
+                materialized.name_machine_translated.clone(),
+                incoming.name_machine_translated.clone(),
+            ),
+            description_original: changed_value(
+                materialized.description_original,
+                incoming.description_original,
+            ),
+            description_machine_translated: changed_value(
+                materialized.description_machine_translated.clone(),
+                incoming.description_machine_translated.clone(),
+            ),
+            url: changed_attr(&materialized.url, &incoming.url),
+            image_url: changed_attr(&materialized.image_url, &incoming.image_url),
+            ..ItemModel::new(incoming.item_id.clone())
+        };
+        if event == ItemModel::new(incoming.item_id.clone()) {
+            return None;
+        }
+        let created = incoming.created.unwrap_or_else(|| clock.now());
+        let applied = event.clone().merged_over(materialized.clone()).resolved();
+        Some(ItemModel {
+            created: Some(created),
+            source_id: incoming
+                .source_id
+                .clone()
+                .or(materialized.source_id.clone()),
+            event_id: incoming
+                .event_id
+                .clone()
+                .or_else(|| Some(format!("{}#{created}", incoming.item_id))),
+            hash: Some(applied.hash()),
+            ..event
+        })
+    }
+}
+
+fn changed_value<T: PartialEq>(materialized: Option<T>, incoming: Option<T>) -> Option<T> {
+    incoming.filter(|incoming| materialized.as_ref() != Some(incoming))
+}
+
+fn changed_attr<T: PartialEq + Clone>(materialized: &Patch<T>, incoming: &Patch<T>) -> Patch<T> {
+    match (materialized.value(), incoming.value()) {
+        (materialized, Some(incoming)) if materialized != Some(incoming) => {
+            Patch::Set(incoming.clone())
         }
+        (Some(_), None) => Patch::Cleared,
+        _ => Patch::Unchanged,
+    }
+}
+
+impl TryFrom<&[ItemModel]> for ItemModel {
+    type Error = Error;
+
+    /// Convert item-events - sorted by latest (first) - to materialized item,
+    /// see [`Materialization::Strict`].
+    fn try_from(item_events: &[ItemModel]) -> Result<Self, Self::Error> {
+        ItemModel::materialize(item_events, Materialization::Strict)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::FixedClock;
     use rstest::rstest;
 
     #[test]
@@ -297,20 +652,20 @@ mod tests {
     #[test]
     fn should_serialize_created_as_sk_with_prefix_item() {
         let item = ItemModel::new("123456".to_string())
-            .created("abcdef".to_string())
+            .created("2010-01-01T11:00:00.001Z".parse().unwrap())
             .to_owned();
-        let expected = r#""sk":"item#abcdef""#;
+        let expected = r#""sk":"item#2010-01-01T11:00:00.001Z""#;
 
         let actual = serde_json::to_string(&item).unwrap();
 
-        assert!(actual.contains(&expected));
+        assert!(actual.contains(expected));
     }
 
     #[test]
     fn should_deserialize_created_as_sk_with_prefix_item() {
-        let item_json = r#"{"pk":"item#123456", "sk":"item#abcdef"}"#;
+        let item_json = r#"{"pk":"item#123456", "sk":"item#2010-01-01T11:00:00.001Z"}"#;
         let expected = ItemModel::new("123456".to_string())
-            .created("abcdef".to_string())
+            .created("2010-01-01T11:00:00.001Z".parse().unwrap())
             .to_owned();
 
         let actual = serde_json::from_str::<ItemModel>(item_json).unwrap();
@@ -318,6 +673,34 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn should_deserialize_legacy_offset_created_normalized_to_utc() {
+        let json = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T12:00:00.001+01:00","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T12:00:00.001+01:00","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
+
+        let actual = serde_json::from_str::<ItemModel>(json).unwrap();
+        let serialized = serde_json::to_string(&actual).unwrap();
+
+        assert_eq!(actual.created, "2010-01-01T11:00:00.001Z".parse().ok());
+        assert_eq!(
+            actual.event_id,
+            Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string())
+        );
+        assert!(serialized.contains(r#""sk":"item#2010-01-01T11:00:00.001Z""#));
+    }
+
+    #[test]
+    fn should_reject_malformed_created() {
+        let item_json = r#"{"pk":"item#123456", "sk":"item#2010-01-01 12:00"}"#;
+
+        let actual = serde_json::from_str::<ItemModel>(item_json).unwrap_err();
+
+        assert!(
+            actual
+                .to_string()
+                .contains("malformed RFC 3339 timestamp '2010-01-01 12:00'")
+        );
+    }
+
     #[test]
     fn should_serialize_source_id_as_party_id_with_prefix_source() {
         let item = ItemModel::new("123456".to_string())
@@ -327,7 +710,7 @@ mod tests {
 
         let actual = serde_json::to_string(&item).unwrap();
 
-        assert!(actual.contains(&expected));
+        assert!(actual.contains(expected));
     }
 
     #[test]
@@ -351,7 +734,7 @@ mod tests {
 
         let actual = serde_json::to_string(&item).unwrap();
 
-        assert!(actual.contains(&expected));
+        assert!(actual.contains(expected));
     }
 
     #[test]
@@ -408,24 +791,34 @@ mod tests {
     fn should_serialize_model_directly() {
         let model = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::from_major(42)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"
+                    .parse()
+                    .unwrap(),
             ),
         };
 
-        let expected = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T12:00:00.001+01:00","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T12:00:00.001+01:00","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
+        let expected = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T11:00:00.001Z","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T11:00:00.001Z","state":"item#AVAILABLE","price":"42","category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
 
         let actual = serde_json::to_string(&model).unwrap();
 
@@ -434,23 +827,33 @@ mod tests {
 
     #[test]
     fn should_deserialize_model_directly() {
-        let json = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T12:00:00.001+01:00","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T12:00:00.001+01:00","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
+        let json = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T11:00:00.001Z","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T11:00:00.001Z","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
         let expected = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::from_major(42)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"
+                    .parse()
+                    .unwrap(),
             ),
         };
 
@@ -463,24 +866,34 @@ mod tests {
     fn should_serialize_model_indirectly() {
         let model = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::from_major(42)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"
+                    .parse()
+                    .unwrap(),
             ),
         };
 
-        let expected = r#"{"category":"foo","description_de":"basis","description_en":"baz","event_id":"item#https://foo.bar#123456#2010-01-01T12:00:00.001+01:00","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b","image_url":"https://foo.bar?item_img=123456","name_de":"balken","name_en":"bar","party_id":"source#https://foo.bar","pk":"item#https://foo.bar#123456","price":42.0,"sk":"item#2010-01-01T12:00:00.001+01:00","state":"item#AVAILABLE","url":"https://foo.bar?item=123456"}"#;
+        let expected = r#"{"category":"foo","description_de":"basis","description_en":"baz","event_id":"item#https://foo.bar#123456#2010-01-01T11:00:00.001Z","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b","image_url":"https://foo.bar?item_img=123456","name_de":"balken","name_en":"bar","party_id":"source#https://foo.bar","pk":"item#https://foo.bar#123456","price":"42","sk":"item#2010-01-01T11:00:00.001Z","state":"item#AVAILABLE","url":"https://foo.bar?item=123456"}"#;
 
         let val = serde_json::to_value(&model).unwrap();
         let actual = serde_json::to_string(&val).unwrap();
@@ -490,23 +903,33 @@ mod tests {
 
     #[test]
     fn should_deserialize_model_indirectly() {
-        let json = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T12:00:00.001+01:00","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T12:00:00.001+01:00","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
+        let json = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T11:00:00.001Z","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T11:00:00.001Z","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
         let expected = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::from_major(42)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"
+                    .parse()
+                    .unwrap(),
             ),
         };
 
@@ -520,20 +943,30 @@ mod tests {
     fn should_round_trip_serialize_eq_deserialize() {
         let item = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::from_major(42)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"
+                    .parse()
+                    .unwrap(),
             ),
         };
         
@@ -545,9 +978,9 @@ mod tests {
 
     #[test]
     fn should_round_trip_deserialize_eq_serialize() {
-        let serialized = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T12:00:00.001+01:00","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T12:00:00.001+01:00","state":"item#AVAILABLE","price":42.0,"category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
+        let serialized = r#"{"pk":"item#https://foo.bar#123456","sk":"item#2010-01-01T11:00:00.001Z","party_id":"source#https://foo.bar","event_id":"item#https://foo.bar#123456#2010-01-01T11:00:00.001Z","state":"item#AVAILABLE","price":"42","category":"foo","name_en":"bar","description_en":"baz","name_de":"balken","description_de":"basis","url":"https://foo.bar?item=123456","image_url":"https://foo.bar?item_img=123456","hash":"1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"}"#;
 
-        let item: ItemModel = serde_json::from_str(&serialized).unwrap();
+        let item: ItemModel = serde_json::from_str(serialized).unwrap();
         let deserialized = serde_json::to_string(&item).unwrap();
 
         assert_eq!(serialized, deserialized);
@@ -557,31 +990,41 @@ mod tests {
     fn should_convert_model_into_data() {
         let model = ItemModel {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
-            event_id: Some("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string()),
+            event_id: Some("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(42f32),
-            category: Some("foo".to_string()),
-            name_en: Some("bar".to_string()),
-            description_en: Some("baz".to_string()),
-            name_de: Some("balken".to_string()),
-            description_de: Some("basis".to_string()),
-            url: Some("https://foo.bar?item=123456".to_string()),
-            image_url: Some("https://foo.bar?item_img=123456".to_string()),
+            price: Some(Money::from_major(42)),
+            category: Patch::Set("foo".to_string()),
+            name_en: Patch::Set("bar".to_string()),
+            description_en: Patch::Set("baz".to_string()),
+            name_de: Patch::Set("balken".to_string()),
+            description_de: Patch::Set("basis".to_string()),
+            name_fr: Patch::Unchanged,
+            description_fr: Patch::Unchanged,
+            name_es: Patch::Unchanged,
+            description_es: Patch::Unchanged,
+            name_original: None,
+            name_machine_translated: None,
+            description_original: None,
+            description_machine_translated: None,
+            url: Patch::Set("https://foo.bar?item=123456".to_string()),
+            image_url: Patch::Set("https://foo.bar?item_img=123456".to_string()),
             hash: Some(
-                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b".to_string(),
+                "1d10a63438fff3ccd4877c2195c0a377a6ee0c8caad97e652b1e69c68b45557b"
+                    .parse()
+                    .unwrap(),
             ),
         };
         let expected = ItemData {
             item_id: "https://foo.bar#123456".to_string(),
-            created: Some("2010-01-01T12:00:00.001+01:00".to_string()),
+            created: Some("2010-01-01T11:00:00.001Z".parse().unwrap()),
             source_id: Some("https://foo.bar".to_string()),
             state: Some(ItemState::AVAILABLE),
-            price: Some(Price::new(EUR, 42f32)),
+            price: Some(Price::new(EUR, Money::from_major(42))),
             category: Some("foo".to_string()),
-            name: HashMap::from([(EN, "bar".to_string()), (DE, "balken".to_string())]),
-            description: HashMap::from([(EN, "baz".to_string()), (DE, "basis".to_string())]),
+            name: LocalizedText::from([(EN, "bar".to_string()), (DE, "balken".to_string())]),
+            description: LocalizedText::from([(EN, "baz".to_string()), (DE, "basis".to_string())]),
             url: Some("https://foo.bar?item=123456".to_string()),
             image_url: Some("https://foo.bar?item_img=123456".to_string()),
         };
@@ -593,33 +1036,33 @@ mod tests {
 
     #[test]
     fn should_materialize_item_events_for_into_model() {
-        let item_events = vec![
+        let item_events = [
             ItemModel::new("foo#123456".to_string())
-                .created("2010-01-04T12:00:00.001+01:00".to_string())
+                .created("2010-01-04T11:00:00.001Z".parse().unwrap())
                 .source_id("https://foo.bar".to_string())
-                .event_id("https://foo.bar#123456#2010-01-04T12:00:00.001+01:00".to_string())
+                .event_id("https://foo.bar#123456#2010-01-04T11:00:00.001Z".to_string())
                 .state(ItemState::SOLD)
                 .to_owned(),
             ItemModel::new("foo#123456".to_string())
-                .created("2010-01-03T12:00:00.001+01:00".to_string())
+                .created("2010-01-03T11:00:00.001Z".parse().unwrap())
                 .source_id("https://foo.bar".to_string())
-                .event_id("https://foo.bar#123456#2010-01-03T12:00:00.001+01:00".to_string())
+                .event_id("https://foo.bar#123456#2010-01-03T11:00:00.001Z".to_string())
                 .state(ItemState::AVAILABLE)
-                .price(37f32)
+                .price(Money::from_major(37))
                 .to_owned(),
             ItemModel::new("foo#123456".to_string())
-                .created("2010-01-02T12:00:00.001+01:00".to_string())
+                .created("2010-01-02T11:00:00.001Z".parse().unwrap())
                 .source_id("https://foo.bar".to_string())
-                .event_id("https://foo.bar#123456#2010-01-02T12:00:00.001+01:00".to_string())
+                .event_id("https://foo.bar#123456#2010-01-02T11:00:00.001Z".to_string())
                 .state(ItemState::AVAILABLE)
-                .price(42f32)
+                .price(Money::from_major(42))
                 .to_owned(),
             ItemModel::new("foo#123456".to_string())
-                .created("2010-01-01T12:00:00.001+01:00".to_string())
+                .created("2010-01-01T11:00:00.001Z".parse().unwrap())
                 .source_id("https://foo.bar".to_string())
-                .event_id("https://foo.bar#123456#2010-01-01T12:00:00.001+01:00".to_string())
+                .event_id("https://foo.bar#123456#2010-01-01T11:00:00.001Z".to_string())
                 .state(ItemState::LISTED)
-                .price(42f32)
+                .price(Money::from_major(42))
                 .category("foo".to_string())
                 .name_en("bar".to_string())
                 .description_en("baz".to_string())
@@ -630,11 +1073,11 @@ mod tests {
                 .to_owned(),
         ];
         let expected = ItemModel::new("foo#123456".to_string())
-            .created("2010-01-04T12:00:00.001+01:00".to_string())
+            .created("2010-01-04T11:00:00.001Z".parse().unwrap())
             .source_id("https://foo.bar".to_string())
-            .event_id("https://foo.bar#123456#2010-01-04T12:00:00.001+01:00".to_string())
+            .event_id("https://foo.bar#123456#2010-01-04T11:00:00.001Z".to_string())
             .state(ItemState::SOLD)
-            .price(37f32)
+            .price(Money::from_major(37))
             .category("foo".to_string())
             .name_en("bar".to_string())
             .description_en("baz".to_string())
@@ -644,9 +1087,412 @@ mod tests {
             .image_url("https://foo.bar?item_img=123456".to_string())
             .to_owned();
 
-        let actual: Result<ItemModel, String> = ItemModel::try_from(&item_events[..]);
+        let actual: Result<ItemModel, Error> = ItemModel::try_from(&item_events[..]);
 
         assert!(actual.is_ok());
         assert_eq!(actual.unwrap(), expected)
     }
+
+    #[test]
+    fn should_honour_cleared_attributes_when_materializing() {
+        let newest =
+            r#"{"pk":"item#foo#123456","sk":"item#2010-01-03T11:00:00.001Z","image_url":null}"#;
+        let item_events = [
+            serde_json::from_str::<ItemModel>(newest).unwrap(),
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-02T11:00:00.001Z".parse().unwrap())
+                .url("https://foo.bar?item=123456".to_string())
+                .to_owned(),
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+                .category("foo".to_string())
+                .image_url("https://foo.bar?item_img=123456".to_string())
+                .to_owned(),
+        ];
+
+        let actual = ItemModel::try_from(&item_events[..]).unwrap();
+
+        assert_eq!(item_events[0].image_url, Patch::Cleared);
+        assert_eq!(actual.image_url, Patch::Unchanged);
+        assert_eq!(actual.category, Patch::Set("foo".to_string()));
+        assert_eq!(
+            actual.url,
+            Patch::Set("https://foo.bar?item=123456".to_string())
+        );
+        assert!(
+            !serde_json::to_string(&actual)
+                .unwrap()
+                .contains("image_url")
+        );
+    }
+
+    #[test]
+    fn should_serialize_cleared_attribute_as_null() {
+        let mut item = ItemModel::new("123456".to_string());
+        item.category = Patch::Cleared;
+
+        let actual = serde_json::to_string(&item).unwrap();
+
+        assert_eq!(actual, r#"{"pk":"item#123456","category":null}"#);
+    }
+
+    #[test]
+    fn should_reject_empty_item_events() {
+        let actual = ItemModel::try_from(&[][..]);
+
+        assert_eq!(actual, Err(Error::EmptyEvents));
+    }
+
+    #[test]
+    fn should_reject_item_events_of_other_items() {
+        let item_events = [
+            ItemModel::new("foo#123456".to_string()),
+            ItemModel::new("foo#654321".to_string())
+                .event_id("foo#654321#2010-01-01T11:00:00.001Z".to_string())
+                .to_owned(),
+        ];
+
+        let actual = ItemModel::try_from(&item_events[..]);
+
+        assert_eq!(
+            actual,
+            Err(Error::MismatchedItemIds {
+                index: 1,
+                expected: "foo#123456".to_string(),
+                found: "foo#654321".to_string(),
+                event_id: Some("foo#654321#2010-01-01T11:00:00.001Z".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_item_events_sorted_oldest_first() {
+        let item_events = [
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+                .to_owned(),
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-02T11:00:00.001Z".parse().unwrap())
+                .event_id("foo#123456#2010-01-02T11:00:00.001Z".to_string())
+                .to_owned(),
+        ];
+
+        let actual = ItemModel::materialize(&item_events, Materialization::Strict);
+
+        assert_eq!(
+            actual,
+            Err(Error::UnsortedEvents {
+                index: 1,
+                event_id: Some("foo#123456#2010-01-02T11:00:00.001Z".to_string()),
+                created: "2010-01-02T11:00:00.001Z".parse().unwrap(),
+                previous: "2010-01-01T11:00:00.001Z".parse().unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_item_event_without_created_when_strict() {
+        let item_events = [
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+                .to_owned(),
+            ItemModel::new("foo#123456".to_string()),
+        ];
+
+        let actual = ItemModel::materialize(&item_events, Materialization::Strict);
+
+        assert_eq!(
+            actual,
+            Err(Error::MissingCreated {
+                index: 1,
+                event_id: None
+            })
+        );
+    }
+
+    #[test]
+    fn should_sort_item_events_when_lenient() {
+        let item_events = [
+            ItemModel::new("foo#123456".to_string())
+                .price(Money::from_major(1))
+                .to_owned(),
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-01T11:00:00.001Z".parse().unwrap())
+                .state(ItemState::LISTED)
+                .price(Money::from_major(42))
+                .to_owned(),
+            ItemModel::new("foo#123456".to_string())
+                .created("2010-01-02T11:00:00.001Z".parse().unwrap())
+                .state(ItemState::SOLD)
+                .to_owned(),
+        ];
+
+        let actual = ItemModel::materialize(&item_events, Materialization::Lenient).unwrap();
+
+        assert_eq!(actual.created, "2010-01-02T11:00:00.001Z".parse().ok());
+        assert_eq!(actual.state, Some(ItemState::SOLD));
+        assert_eq!(actual.price, Some(Money::from_major(42)));
+    }
+
+    #[test]
+    fn should_reject_item_events_of_other_items_when_lenient() {
+        let item_events = [
+            ItemModel::new("foo#123456".to_string()),
+            ItemModel::new("foo#654321".to_string()),
+        ];
+
+        let actual = ItemModel::materialize(&item_events, Materialization::Lenient);
+
+        assert!(matches!(
+            actual,
+            Err(Error::MismatchedItemIds { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn should_name_field_and_prefix_when_deserializing_without_prefix() {
+        let item_json = r#"{"pk":"item#123456", "party_id":"abcdef"}"#;
+
+        let actual = serde_json::from_str::<ItemModel>(item_json).unwrap_err();
+
+        assert!(
+            actual
+                .to_string()
+                .contains("'party_id' value 'abcdef' lacks prefix 'source#'")
+        );
+    }
+
+    fn scraped(created: &str) -> ItemModel {
+        ItemModel::new("foo#123456".to_string())
+            .created(created.parse().unwrap())
+            .source_id("foo".to_string())
+            .event_id(format!("foo#123456#{created}"))
+            .state(ItemState::AVAILABLE)
+            .price(Money::from_major(42))
+            .name_en("bar".to_string())
+            .image_url("https://foo.bar?item_img=123456".to_string())
+            .to_owned()
+    }
+
+    #[test]
+    fn should_diff_to_none_when_nothing_changed() {
+        let materialized = scraped("2010-01-01T11:00:00.001Z");
+        let incoming = scraped("2010-01-02T11:00:00.001Z")
+            .price(Money::new(4200, 2))
+            .to_owned();
+
+        let actual = ItemModel::diff(&materialized, &incoming);
+
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn should_diff_to_sparse_event_with_changed_price() {
+        let materialized = scraped("2010-01-01T11:00:00.001Z");
+        let incoming = scraped("2010-01-02T11:00:00.001Z")
+            .price(Money::from_major(37))
+            .to_owned();
+        let expected = ItemModel::new("foo#123456".to_string())
+            .created("2010-01-02T11:00:00.001Z".parse().unwrap())
+            .source_id("foo".to_string())
+            .event_id("foo#123456#2010-01-02T11:00:00.001Z".to_string())
+            .price(Money::from_major(37))
+            .to_owned();
+
+        let actual = ItemModel::diff(&materialized, &incoming).unwrap();
+
+        assert_eq!(actual.hash, Some(incoming.hash()));
+        assert_eq!(
+            ItemModel {
+                hash: None,
+                ..actual
+            },
+            expected
+        );
+    }
+
+    #[test]
+    fn should_clear_attributes_missing_from_incoming_item() {
+        let materialized = scraped("2010-01-01T11:00:00.001Z");
+        let mut incoming = scraped("2010-01-02T11:00:00.001Z");
+        incoming.image_url = Patch::Unchanged;
+        incoming.name_de = Patch::Set("balken".to_string());
+        incoming.state = None;
+
+        let actual = ItemModel::diff(&materialized, &incoming).unwrap();
+
+        assert_eq!(actual.image_url, Patch::Cleared);
+        assert_eq!(actual.name_de, Patch::Set("balken".to_string()));
+        assert_eq!(actual.name_en, Patch::Unchanged);
+        assert_eq!(actual.state, None);
+        assert_eq!(
+            actual.hash,
+            Some(
+                ItemModel {
+                    state: materialized.state,
+                    ..incoming
+                }
+                .hash()
+            )
+        );
+    }
+
+    #[test]
+    fn should_stamp_diff_without_created_from_clock() {
+        let materialized = scraped("2010-01-01T11:00:00.001Z");
+        let mut incoming = scraped("2010-01-02T11:00:00.001Z")
+            .price(Money::from_major(37))
+            .to_owned();
+        incoming.created = None;
+        incoming.event_id = None;
+        let clock = FixedClock("2010-01-03T11:00:00.001Z".parse().unwrap());
+
+        let event = ItemModel::diff_with(&materialized, &incoming, &clock).unwrap();
+
+        assert_eq!(event.created, Some(clock.0));
+        assert_eq!(
+            event.event_id,
+            Some("foo#123456#2010-01-03T11:00:00.001Z".to_string())
+        );
+        assert!(ItemModel::try_from(&[event, materialized][..]).is_ok());
+    }
+
+    #[test]
+    fn should_materialize_diff_like_incoming_item() {
+        let materialized = scraped("2010-01-01T11:00:00.001Z");
+        let mut incoming = scraped("2010-01-02T11:00:00.001Z")
+            .state(ItemState::SOLD)
+            .category("foo".to_string())
+            .to_owned();
+        incoming.image_url = Patch::Unchanged;
+
+        let event = ItemModel::diff(&materialized, &incoming).unwrap();
+        let actual = ItemModel::try_from(&[event, materialized][..]).unwrap();
+
+        assert_eq!(
+            actual,
+            ItemModel {
+                hash: Some(incoming.hash()),
+                ..incoming
+            }
+        );
+    }
+
+    fn history_newest_first() -> Vec<ItemModel> {
+        let event = |created: &str| {
+            ItemModel::new("foo#123456".to_string())
+                .created(created.parse().unwrap())
+                .to_owned()
+        };
+        let mut cleared = event("2010-01-03T11:00:00.001Z")
+            .state(ItemState::SOLD)
+            .to_owned();
+        cleared.category = Patch::Cleared;
+        vec![
+            cleared,
+            event("2010-01-02T11:00:00.001Z")
+                .price(Money::from_major(37))
+                .to_owned(),
+            event("2010-01-01T11:00:00.001Z")
+                .state(ItemState::AVAILABLE)
+                .price(Money::from_major(42))
+                .category("foo".to_string())
+                .to_owned(),
+        ]
+    }
+
+    #[rstest]
+    #[case("2009-12-31T11:00:00.000Z", None)]
+    #[case("2010-01-01T11:00:00.001Z", Some((ItemState::AVAILABLE, 42)))]
+    #[case("2010-01-02T23:59:59.999Z", Some((ItemState::AVAILABLE, 37)))]
+    #[case("2011-01-01T00:00:00.000Z", Some((ItemState::SOLD, 37)))]
+    fn should_materialize_as_of_instant(
+        #[case] instant: &str,
+        #[case] expected: Option<(ItemState, i64)>,
+    ) {
+        let actual =
+            ItemModel::materialize_as_of(&history_newest_first(), instant.parse().unwrap())
+                .unwrap();
+
+        assert_eq!(
+            actual.map(|item| (item.state.unwrap(), item.price.unwrap())),
+            expected.map(|(state, price)| (state, Money::from_major(price)))
+        );
+    }
+
+    #[test]
+    fn should_materialize_latest_state_as_of_now() {
+        let item_events = history_newest_first();
+
+        let actual = ItemModel::materialize_as_of(&item_events, Timestamp::now());
+
+        assert_eq!(actual, ItemModel::try_from(&item_events[..]).map(Some));
+    }
+
+    #[test]
+    fn should_reject_unsorted_item_events_as_of_instant() {
+        let mut item_events = history_newest_first();
+        item_events.reverse();
+
+        let actual = ItemModel::materialize_as_of(&item_events, Timestamp::now());
+
+        assert!(matches!(
+            actual,
+            Err(Error::UnsortedEvents { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn should_materialize_every_state_between_instants() {
+        let item_events = history_newest_first();
+
+        let actual = ItemModel::materialize_between(
+            &item_events,
+            "2010-01-01T12:00:00.000Z".parse().unwrap(),
+            "2010-01-03T11:00:00.001Z".parse().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual
+                .iter()
+                .map(|item| (item.state, item.price, item.category.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Some(ItemState::AVAILABLE),
+                    Some(Money::from_major(42)),
+                    Patch::Set("foo".to_string())
+                ),
+                (
+                    Some(ItemState::AVAILABLE),
+                    Some(Money::from_major(37)),
+                    Patch::Set("foo".to_string())
+                ),
+                (
+                    Some(ItemState::SOLD),
+                    Some(Money::from_major(37)),
+                    Patch::Unchanged
+                ),
+            ]
+        );
+        assert_eq!(actual[2], ItemModel::try_from(&item_events[..]).unwrap());
+    }
+
+    #[test]
+    fn should_start_between_instants_with_first_item_event() {
+        let item_events = history_newest_first();
+
+        let actual = ItemModel::materialize_between(
+            &item_events,
+            "2009-01-01T00:00:00.000Z".parse().unwrap(),
+            "2010-01-02T00:00:00.000Z".parse().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual,
+            vec![ItemModel::try_from(&item_events[2..]).unwrap()]
+        );
+    }
 }
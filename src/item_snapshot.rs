@@ -0,0 +1,271 @@
+use crate::error::Error;
+use crate::item_model::{ItemModel, check_newest_first};
+use crate::timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// An item materialized from its item-events up to and including `created`, stored
+/// next to them under the sort-key `item#snapshot#<created>`.
+///
+/// Snapshot sort-keys sort after all item-event ones (`item#<created>`), but share
+/// their `item#` prefix: query item-events only with `sk < "item#s"` and snapshots
+/// with `begins_with(sk, "item#snapshot#")`. An [`ItemModel`] rejects a snapshot row
+/// with [`Error::SnapshotSortKey`].
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct ItemSnapshot {
+    // created of the newest item-event covered
+    #[serde(
+        rename = "sk",
+        serialize_with = "crate::ddb_prefix::ser_sk_snapshot_prefix",
+        deserialize_with = "crate::ddb_prefix::de_sk_snapshot_prefix"
+    )]
+    created: Timestamp,
+
+    // without created, which is stored as sk above
+    #[serde(flatten)]
+    item: ItemModel,
+}
+
+impl ItemSnapshot {
+    pub fn created(&self) -> Timestamp {
+        self.created
+    }
+
+    pub fn item_id(&self) -> &str {
+        &self.item.item_id
+    }
+
+    /// The materialized item at the time of the snapshot.
+    pub fn item(&self) -> ItemModel {
+        self.clone().into_item()
+    }
+
+    pub fn into_item(self) -> ItemModel {
+        ItemModel {
+            created: Some(self.created),
+            ..self.item
+        }
+    }
+
+    /// Whether the snapshot already includes `item_event`, so it need not be read.
+    pub fn covers(&self, item_event: &ItemModel) -> bool {
+        item_event
+            .created
+            .is_none_or(|created| created <= self.created)
+    }
+}
+
+impl TryFrom<&[ItemModel]> for ItemSnapshot {
+    type Error = Error;
+
+    /// Snapshot item-events - sorted by latest (first) - see [`ItemModel::try_from`].
+    fn try_from(item_events: &[ItemModel]) -> Result<Self, Self::Error> {
+        let mut item = ItemModel::try_from(item_events)?;
+        Ok(ItemSnapshot {
+            // strict materialization requires created
+            created: item.created.take().expect("created of materialized item"),
+            item,
+        })
+    }
+}
+
+impl ItemModel {
+    /// Like [`ItemModel::try_from`], but stops at the item-events covered by
+    /// `snapshot` and merges the newer ones onto it instead. All item-events must
+    /// be sorted by latest (first), covered ones included.
+    pub fn materialize_from(
+        snapshot: Option<&ItemSnapshot>,
+        item_events: &[ItemModel],
+    ) -> Result<Self, Error> {
+        let Some(snapshot) = snapshot else {
+            return ItemModel::try_from(item_events);
+        };
+        check_newest_first(item_events)?;
+        let mut newer: Vec<ItemModel> = item_events
+            .iter()
+            .take_while(|event| !snapshot.covers(event))
+            .cloned()
+            .collect();
+        newer.push(snapshot.item());
+        ItemModel::try_from(&newer[..])
+    }
+}
+
+/// Result of [`compact`]: a snapshot replacing the item-events it covers.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Compaction {
+    pub snapshot: ItemSnapshot,
+    /// Covered item-events, safe to delete once `snapshot` is stored.
+    pub deletable: Vec<ItemModel>,
+    /// The previous snapshot, if the new one supersedes it.
+    pub superseded: Option<ItemSnapshot>,
+}
+
+/// Compact an item's history - the `previous` snapshot, if any, and the item-events
+/// sorted by latest (first) - into a single snapshot of the newest state.
+pub fn compact(
+    previous: Option<&ItemSnapshot>,
+    item_events: &[ItemModel],
+) -> Result<Compaction, Error> {
+    let item = ItemModel::materialize_from(previous, item_events)?;
+    let snapshot = match previous {
+        Some(previous) if item.created == Some(previous.created) => previous.clone(),
+        _ => ItemSnapshot::try_from(&[item][..])?,
+    };
+    let superseded = previous.filter(|previous| **previous != snapshot).cloned();
+    Ok(Compaction {
+        deletable: item_events.to_vec(),
+        snapshot,
+        superseded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::item_model::ItemModel;
+    use crate::item_snapshot::{ItemSnapshot, compact};
+    use crate::item_state::ItemState;
+    use crate::money::Money;
+    use crate::test_support::event;
+
+    fn item_events_newest_first() -> Vec<ItemModel> {
+        vec![
+            event("2010-01-04T11:00:00.001Z")
+                .state(ItemState::SOLD)
+                .to_owned(),
+            event("2010-01-03T11:00:00.001Z")
+                .price(Money::from_major(37))
+                .to_owned(),
+            event("2010-01-02T11:00:00.001Z")
+                .state(ItemState::AVAILABLE)
+                .to_owned(),
+            event("2010-01-01T11:00:00.001Z")
+                .state(ItemState::LISTED)
+                .price(Money::from_major(42))
+                .category("foo".to_string())
+                .to_owned(),
+        ]
+    }
+
+    #[test]
+    fn should_serialize_created_as_sk_with_prefix_snapshot() {
+        let snapshot = ItemSnapshot::try_from(&[event("2010-01-01T11:00:00.001Z")][..]).unwrap();
+        let expected = r#"{"sk":"item#snapshot#2010-01-01T11:00:00.001Z","pk":"item#foo#123456","event_id":"item#foo#123456#2010-01-01T11:00:00.001Z"}"#;
+
+        let actual = serde_json::to_string(&snapshot).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_round_trip_snapshot() {
+        let snapshot = ItemSnapshot::try_from(&item_events_newest_first()[..]).unwrap();
+
+        let serialized = serde_json::to_string(&snapshot).unwrap();
+        let actual: ItemSnapshot = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(actual, snapshot);
+    }
+
+    #[test]
+    fn should_reject_item_event_as_snapshot() {
+        let item_json = r#"{"pk":"item#foo#123456","sk":"item#2010-01-01T11:00:00.001Z"}"#;
+
+        let actual = serde_json::from_str::<ItemSnapshot>(item_json).unwrap_err();
+
+        assert!(actual.to_string().contains("lacks prefix 'item#snapshot#'"));
+    }
+
+    #[test]
+    fn should_materialize_from_snapshot_like_from_all_item_events() {
+        let item_events = item_events_newest_first();
+        let snapshot = ItemSnapshot::try_from(&item_events[2..]).unwrap();
+
+        let actual = ItemModel::materialize_from(Some(&snapshot), &item_events);
+
+        assert_eq!(actual, ItemModel::try_from(&item_events[..]));
+    }
+
+    #[test]
+    fn should_stop_at_snapshot() {
+        let item_events = item_events_newest_first();
+        let snapshot = ItemSnapshot::try_from(&item_events[2..]).unwrap();
+        // covered item-events are not merged again
+        let mut stored = item_events[..2].to_vec();
+        stored.push(event("2010-01-02T11:00:00.001Z"));
+
+        let actual = ItemModel::materialize_from(Some(&snapshot), &stored).unwrap();
+
+        assert_eq!(actual, ItemModel::try_from(&item_events[..]).unwrap());
+    }
+
+    #[test]
+    fn should_reject_unsorted_item_events_before_stopping_at_snapshot() {
+        let item_events = item_events_newest_first();
+        let snapshot = ItemSnapshot::try_from(&item_events[2..]).unwrap();
+        // the newer item-event would be dropped if reading stopped at the covered one
+        let stored = [item_events[3].clone(), item_events[0].clone()];
+
+        let actual = ItemModel::materialize_from(Some(&snapshot), &stored);
+
+        assert!(matches!(
+            actual,
+            Err(Error::UnsortedEvents { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn should_reject_snapshot_row_as_item_event() {
+        let snapshot = ItemSnapshot::try_from(&item_events_newest_first()[..]).unwrap();
+        let serialized = serde_json::to_string(&snapshot).unwrap();
+
+        let actual = serde_json::from_str::<ItemModel>(&serialized).unwrap_err();
+
+        assert!(
+            actual.to_string().contains(
+                &Error::SnapshotSortKey {
+                    value: "item#snapshot#2010-01-04T11:00:00.001Z".to_string(),
+                }
+                .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn should_compact_history_into_snapshot() {
+        let item_events = item_events_newest_first();
+        let previous = ItemSnapshot::try_from(&item_events[3..]).unwrap();
+
+        let actual = compact(Some(&previous), &item_events[..3]).unwrap();
+
+        assert_eq!(
+            actual.snapshot.created(),
+            "2010-01-04T11:00:00.001Z".parse().unwrap()
+        );
+        assert_eq!(
+            actual.snapshot.item(),
+            ItemModel::try_from(&item_events[..]).unwrap()
+        );
+        assert_eq!(actual.deletable, item_events[..3].to_vec());
+        assert_eq!(actual.superseded, Some(previous));
+    }
+
+    #[test]
+    fn should_keep_snapshot_without_newer_item_events() {
+        let item_events = item_events_newest_first();
+        let previous = ItemSnapshot::try_from(&item_events[..]).unwrap();
+
+        let actual = compact(Some(&previous), &[]).unwrap();
+
+        assert_eq!(actual.snapshot, previous);
+        assert_eq!(actual.deletable, vec![]);
+        assert_eq!(actual.superseded, None);
+    }
+
+    #[test]
+    fn should_reject_compacting_nothing() {
+        let actual = compact(None, &[]);
+
+        assert_eq!(actual, Err(Error::EmptyEvents));
+    }
+}
@@ -1,7 +1,11 @@
+use crate::item_model::ItemModel;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use strum_macros::{Display, EnumIter, EnumString};
 
-#[derive(Serialize, Deserialize, Copy, Clone, Display, EnumString, EnumIter, Eq, PartialEq, Debug)]
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Display, EnumString, EnumIter, Eq, PartialEq, Debug,
+)]
 pub enum ItemState {
     LISTED,
     AVAILABLE,
@@ -9,3 +13,185 @@ pub enum ItemState {
     SOLD,
     REMOVED,
 }
+
+impl ItemState {
+    /// States an item may move to from this one. Staying in the same state, e.g. for a
+    /// price update, is always allowed and not listed here.
+    pub fn successors(&self) -> &'static [ItemState] {
+        match self {
+            ItemState::LISTED => &[
+                ItemState::AVAILABLE,
+                ItemState::RESERVED,
+                ItemState::SOLD,
+                ItemState::REMOVED,
+            ],
+            ItemState::AVAILABLE => &[ItemState::RESERVED, ItemState::SOLD, ItemState::REMOVED],
+            ItemState::RESERVED => &[ItemState::AVAILABLE, ItemState::SOLD, ItemState::REMOVED],
+            ItemState::SOLD => &[ItemState::REMOVED],
+            ItemState::REMOVED => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: ItemState) -> bool {
+        *self == next || self.successors().contains(&next)
+    }
+
+    /// Moves to `next`, failing if the lifecycle does not allow it.
+    pub fn transition(self, next: ItemState) -> Result<ItemState, IllegalTransition> {
+        if self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(IllegalTransition {
+                from: self,
+                to: next,
+            })
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct IllegalTransition {
+    pub from: ItemState,
+    pub to: ItemState,
+}
+
+impl fmt::Display for IllegalTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "illegal item state transition {} -> {}",
+            self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for IllegalTransition {}
+
+/// An illegal transition found in an event history, with the event that made it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HistoryViolation {
+    pub event_id: Option<String>,
+    pub transition: IllegalTransition,
+}
+
+impl fmt::Display for HistoryViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.event_id {
+            Some(event_id) => write!(f, "{} in event '{event_id}'", self.transition),
+            None => write!(f, "{} in event without id", self.transition),
+        }
+    }
+}
+
+impl std::error::Error for HistoryViolation {}
+
+/// Checks the state transitions of item-events - sorted by latest (first), as for
+/// materialization - and reports every illegal one. Events without state are skipped;
+/// after an illegal transition the history continues from the state it moved to.
+pub fn validate_history(item_events: &[ItemModel]) -> Result<(), Vec<HistoryViolation>> {
+    let mut violations = Vec::new();
+    let mut current: Option<ItemState> = None;
+    for event in item_events.iter().rev() {
+        let Some(next) = event.state else {
+            continue;
+        };
+        if let Some(Err(transition)) = current.map(|state| state.transition(next)) {
+            violations.push(HistoryViolation {
+                event_id: event.event_id.clone(),
+                transition,
+            });
+        }
+        current = Some(next);
+    }
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::item_state::ItemState::{AVAILABLE, LISTED, REMOVED, RESERVED, SOLD};
+    use crate::item_state::{HistoryViolation, IllegalTransition, ItemState, validate_history};
+    use crate::test_support::event;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(LISTED, AVAILABLE, true)]
+    #[case(AVAILABLE, AVAILABLE, true)]
+    #[case(AVAILABLE, RESERVED, true)]
+    #[case(RESERVED, AVAILABLE, true)]
+    #[case(RESERVED, SOLD, true)]
+    #[case(SOLD, REMOVED, true)]
+    #[case(AVAILABLE, LISTED, false)]
+    #[case(SOLD, LISTED, false)]
+    #[case(SOLD, AVAILABLE, false)]
+    #[case(REMOVED, RESERVED, false)]
+    fn should_follow_transition_table(
+        #[case] from: ItemState,
+        #[case] to: ItemState,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(from.can_transition_to(to), expected);
+    }
+
+    #[test]
+    fn should_return_typed_error_on_illegal_transition() {
+        assert_eq!(RESERVED.transition(SOLD), Ok(SOLD));
+        assert_eq!(
+            SOLD.transition(LISTED),
+            Err(IllegalTransition {
+                from: SOLD,
+                to: LISTED
+            })
+        );
+    }
+
+    #[test]
+    fn should_accept_legal_history() {
+        let item_events = [
+            event("2010-01-04T12:00:00.000Z").state(SOLD).to_owned(),
+            event("2010-01-03T12:00:00.000Z"),
+            event("2010-01-02T12:00:00.000Z")
+                .state(AVAILABLE)
+                .to_owned(),
+            event("2010-01-01T12:00:00.000Z").state(LISTED).to_owned(),
+        ];
+
+        assert_eq!(validate_history(&item_events), Ok(()));
+    }
+
+    #[test]
+    fn should_report_every_illegal_transition_with_event_id() {
+        let item_events = [
+            event("2010-01-05T12:00:00.000Z").state(RESERVED).to_owned(),
+            event("2010-01-04T12:00:00.000Z").state(REMOVED).to_owned(),
+            event("2010-01-03T12:00:00.000Z").state(LISTED).to_owned(),
+            event("2010-01-02T12:00:00.000Z").state(SOLD).to_owned(),
+            event("2010-01-01T12:00:00.000Z").state(LISTED).to_owned(),
+        ];
+
+        let actual = validate_history(&item_events).unwrap_err();
+
+        assert_eq!(
+            actual,
+            vec![
+                HistoryViolation {
+                    event_id: Some("foo#123456#2010-01-03T12:00:00.000Z".to_string()),
+                    transition: IllegalTransition {
+                        from: SOLD,
+                        to: LISTED
+                    },
+                },
+                HistoryViolation {
+                    event_id: Some("foo#123456#2010-01-05T12:00:00.000Z".to_string()),
+                    transition: IllegalTransition {
+                        from: REMOVED,
+                        to: RESERVED
+                    },
+                },
+            ]
+        );
+    }
+}
@@ -1,9 +1,8 @@
-use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter, EnumString};
 
 // ISO 639-1
-#[derive(Serialize, Deserialize, Copy, Clone, Display, EnumString, EnumIter, Eq, PartialEq, Debug, Hash)]
+#[derive(Serialize, Deserialize, Copy, Clone, Display, EnumString, EnumIter, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
     DE,
@@ -12,8 +11,6 @@ pub enum Language {
     ES
 }
 
-pub type I18nString = HashMap<Language, String>;
-
 #[cfg(test)]
 mod tests {
     use crate::language::Language;
@@ -0,0 +1,384 @@
+use crate::language::Language;
+use crate::localized_text::LocalizedText;
+use std::collections::HashMap;
+
+/// The 500 most frequent character trigrams per language, most frequent first. Words
+/// are padded with a space on both sides, so ` de` is a word starting with "de".
+///
+/// Counted over the distinct translated messages of the gettext catalogs (about 35k
+/// messages per language) shipped in a Debian system's `/usr/share/locale`, English
+/// over their source messages. Words were split like [`trigrams`] does, after
+/// removing format placeholders; a fifth of the catalogs was held out for testing.
+const PROFILES: [(Language, &[&str]); 4] = [
+    (
+        Language::DE,
+        &[
+            "en ", "er ", "ich", " de", "ein", "der", "cht", "sch", "ung", "ht ", "te ", "den",
+            " ni", "nic", " da", "ver", " be", "che", "ie ", "nde", "in ", " au", " di", "on ",
+            " ei", "ten", "die", " un", "ate", "es ", " we", "ion", "dat", "ert", "ist", "ier",
+            " in", " ve", "zei", "rde", "ter", "ste", "gen", "nte", "tio", "wer", "ng ", "ben",
+            "ine", "rt ", " an", "end", "st ", "ers", " ge", "tei", "ent", " vo", "nge", "ere",
+            "eic", "ch ", " zu", "nen", " er", " fe", "ne ", " fü", "erd", " si", "eit", "le ",
+            "aus", "ige", "hen", " is", "für", "ür ", " ko", "ehl", "feh", "ei ", "men", " wi",
+            "ell", "sse", "nd ", "ren", "abe", "ann", "ber", "nn ", " ze", " se", "it ", "chl",
+            "kan", "auf", "et ", "tig", "lle", "rei", "des", "und", " sc", "len", " mi", "de ",
+            "von", "lte", "ese", "ges", "ge ", "rte", "sta", " ke", "kei", "ati", "mit", "erw",
+            "wen", "ebe", " ka", " re", "nnt", "geb", "bei", "ame", "hle", "kon", "ang", "alt",
+            "run", "ind", "sie", "im ", " st", "sel", "ern", "rd ", "ode", "lti", "isc", "sen",
+            "wir", "her", " al", " pr", "eru", " pa", "ler", "nam", "and", "gül", "ült", "ird",
+            "uf ", "ite", "üss", "das", " en", "erz", "tzt", "nt ", "zu ", "as ", "el ", "nst",
+            " na", "em ", "rwe", "rze", "ner", "eil", "tel", " le", "lis", "ls ", "kti", "ege",
+            "for", "one", "ger", " ar", "hre", "rst", "ens", " op", "um ", "chr", "lüs", "tze",
+            "hlü", "gab", "lic", "ile", "me ", "us ", " ta", "ach", " ab", "unt", "fun", " od",
+            "etz", "ngü", "he ", "onn", " nu", "esc", "akt", "nis", "lt ", " e ", "vor", "ser",
+            "pti", "art", " so", "ur ", "pro", "ies", "ing", "als", " sp", "zt ", "gt ", "eig",
+            "usg", "set", "ngs", "int", "hl ", "chn", "enn", "tie", "age", "re ", "wei", "all",
+            "tet", "ort", "se ", "übe", "opt", "est", " um", "typ", "oll", "hal", " ha", "eim",
+            "utz", "enu", "ete", "nut", "ll ", "war", " üb", "ss ", " me", "ket", "at ", "ass",
+            " bi", "fen", " ak", "anz", "ene", "ess", " im", " ma", "tab", "be ", "mat", "ele",
+            "erh", "geg", "spe", "mme", "tte", "ien", "sti", "sei", "wur", " wu", "ons", "al ",
+            "det", "orm", "urd", "lie", "ume", "era", " gr", "les", "ins", "tra", " li", "gel",
+            "per", "zer", "lge", "kom", "ahl", "is ", " sy", "erf", "erl", "uch", "iti", "gef",
+            "lau", "ts ", " wa", "änd", "fer", "rti", "rsc", "rma", "hni", "ini", "ech", "nne",
+            "bel", "unk", "nkt", " fo", " ne", "lei", "rch", " ex", "arg", "yp ", "tat", "zah",
+            "res", " mu", "spa", "pal", "tes", "zen", "ale", "ede", "lun", "ign", "rie", "nac",
+            "eis", " te", "chi", "mus", "uss", "wie", "lag", "sin", "erv", " fa", "str", "erb",
+            "efe", "tem", "mer", "rn ", "ord", "lat", "sge", "dar", "dun", "rec", "or ", "ütz",
+            "ast", "rüc", "nze", "zie", "ück", "ig ", "erg", "nur", "stü", "ali", "sio", "leg",
+            "eld", "enb", "its", "rgu", "rbe", "gum", "itt", " co", "id ", "an ", "neu", " tr",
+            "sic", " es", "tiv", " ty", "ble", "tüt", "sig", "rag", "rat", "dem", "pri", "rha",
+            "rla", "par", "tan", "err", "ext", "ktu", "rsi", "ika", "vie", "atu", "bef", "hla",
+            "iel", "wor", "kat", "hri", "tor", "bar", "bt ", "ken", "isi", "ban", "sol", "nba",
+            "ck ", "eib", "chs", "fal", "mal", "ndu", "hte", "ank", "nga", "gli", "cke", "mod",
+            "ram", "ake", "kt ", "ric", " hi", "tri", "rve", "ar ", "arb", "nie", " ob", "inf",
+            " po", "ran", "pei", "omp", "cha", "ifi", " la", "nfo", "hne", "eie", "ppe", "bin",
+            "tas", "eme", "lin", "ekt", "ühr", " no", "met", "zur", "pas", "füh", "zug", " mo",
+            "nal", "rwa", "igt", "amm", "nun", " lo", "urc", "pak", " kö", "uel", "nbe", "rne",
+            "grö", "bes", "äng", "aub", "llt", "ntr", "fol", "kön",
+        ],
+    ),
+    (
+        Language::EN,
+        &[
+            "ed ", " in", "ion", "on ", " th", " re", "tio", "ng ", "ing", "the", " co", "le ",
+            "or ", "er ", " no", "ot ", "not", " to", "he ", "to ", "es ", "ile", " fo", "for",
+            " fi", " se", "ect", "is ", " of", "in ", "ent", "nd ", "of ", "fil", "ati", "ter",
+            " is", "te ", " a ", "nt ", " ca", "ate", "and", "cti", "se ", "ted", " us", "ble",
+            "st ", "re ", " pr", " de", "val", " ex", " un", " be", "con", "use", " an", " pa",
+            " st", " wi", "ame", " li", "abl", "ry ", "can", "me ", " di", "rea", "th ", "id ",
+            " op", " ar", "ut ", "ge ", "res", "al ", " ma", "ali", "com", "ess", "ver", "ist",
+            "ns ", "nam", "an ", "et ", "rec", "cat", "it ", "ith", "ons", " on", "ts ", "ld ",
+            "wit", "all", " ch", "ead", "sta", "be ", " al", "lin", "as ", " en", "ann", "int",
+            "en ", "at ", "lid", "tin", " su", " lo", "loc", "men", "ve ", "ly ", "nno", " do",
+            "tor", "ch ", "ste", "dat", " or", " wh", "pec", "ers", "ine", "ne ", " na", "ll ",
+            "err", "ort", " va", "set", "sio", "out", "tab", "ce ", "inv", "ire", "age", "sec",
+            " si", "ad ", "ail", "pro", "str", "mat", "de ", " sy", " ta", "ins", "nva", "led",
+            "per", "ser", "por", "ran", "pre", "nte", " mo", "ind", " er", "pti", " me", "ssi",
+            "rin", "rro", " fa", " ha", " sh", "exp", "ica", "are", "ror", " sp", "uld", "oul",
+            "cte", "red", " da", "pe ", "ive", "ize", "thi", "cha", " wa", "ata", "nst", "ope",
+            "han", "ign", "cou", "no ", "typ", "ow ", " tr", "ack", "ss ", "ype", "era", " mu",
+            "opt", "omm", "ont", "rat", "sin", "rt ", " ke", "les", "ust", "ct ", "ang", "ore",
+            "his", "orm", " ty", "def", " as", "arg", "par", "ode", " nu", "fai", "key", "ue ",
+            "dir", "ult", "sup", "ifi", "nge", "put", "tri", " fr", " ou", "oca", "rs ", "che",
+            "ory", "her", "rel", "ren", "om ", "ase", "iti", " ne", "upp", "nde", "man", "spe",
+            "num", "act", "ber", "end", "rom", " by", "fie", "eci", "lic", "ck ", "alu", "ove",
+            "ere", "mbe", "lue", "ite", "pac", "oun", "ain", "cre", "eco", "ces", " bu", " e ",
+            " mi", "rma", "omp", " at", " ba", " po", "ppo", " ad", "low", "mod", "ara", "ext",
+            "sym", "enc", "fro", "ze ", "tem", "lis", " ve", " cr", "rit", "emo", "cal", "chi",
+            "sed", "ume", "equ", "dis", "umb", "und", "eat", "rd ", "nin", "din", "ay ", "whe",
+            "tch", "tur", " gi", "own", "add", "mus", "rsi", "llo", " so", "tra", "mbo", "ple",
+            "reg", "nal", "cif", "ic ", "fin", "bol", "der", "ymb", "lt ", "est", "siz", "ol ",
+            " la", "war", "one", "rte", "ds ", "sho", "tru", "col", "lat", " he", "pri", "ure",
+            "pat", "ord", "hen", "har", "cto", " te", "ass", "ey ", "arc", "tat", "tha", "ty ",
+            "elo", "exi", "rem", " le", "ele", "tar", "pla", "has", " ge", "cod", "wor", "fer",
+            " it", "mma", " t ", "rch", "ied", "ta ", " cl", "nly", "by ", "onl", "whi", "get",
+            " ac", "cur", "mis", "but", " wr", "nce", "nta", "req", "ete", "mes", "rge", "you",
+            "ntr", "wn ", "rep", "ten", "rac", "nab", "ide", "unk", "up ", "xpe", "art", "unc",
+            " yo", "ock", "tim", "bas", "tes", "ref", "oes", "egi", "atc", "if ", "pli", "doe",
+            " fu", "cor", "ern", "nts", " ti", " im", "us ", " if", "jec", "app", "ime", "ach",
+            "qui", "nti", "ary", "el ", "ini", "aul", "erv", "pen", "ert", "fau", "efa", "utp",
+            "tpu", "iss", " ob", "em ", "ill", "sig", "git", " up", "our", "ls ", "min", "rti",
+            "now", "wri", "ex ", "pos", "mov", " b ", "inc", "rgu", "gum", "tic", "ner", "too",
+            "mit", " s ", "bje", " ra", "rou", "hil", " ap", "ace", " bi", "ned", "obj", "inf",
+            "gis", "onf", "emp", "pt ", "rre", "kno", "nfo", " sc", "do ", "arn", "ina", "que",
+            "dex", " au", "ram", "nat", "efi", "olu", "ew ", "ena",
+        ],
+    ),
+    (
+        Language::FR,
+        &[
+            " de", "de ", "es ", "le ", "ion", " le", "on ", "er ", "tio", "ur ", " pa", " la",
+            "re ", " co", "la ", "ent", "nt ", "ne ", "les", " un", "ns ", " in", "our", "fic",
+            "pas", "ati", "as ", "eur", "te ", " d ", " po", " l ", "que", " en", "ble", "ich",
+            " no", "men", "ier", " re", "con", "chi", " dé", " fi", "est", " es", "lis", "des",
+            "che", "pou", "tre", "onn", "ue ", "cti", "res", "st ", "un ", "ans", "du ", "hie",
+            " se", " du", " su", "par", "dan", " à ", "rs ", " n ", " ré", "et ", "en ", "ire",
+            " li", "ge ", "ant", "com", " au", " da", "ée ", "une", " ne", "it ", "ect", " pr",
+            "se ", "ons", "uti", "ssi", "eme", "age", "ont", " so", "ess", "val", " do", "iqu",
+            "ce ", "til", "ut ", "nte", "ili", "ver", " a ", "ibl", "ts ", "pos", "ser", "ali",
+            " ex", "ter", " ut", " im", "ise", "ifi", " tr", " pe", "nom", "ist", "sio", "mpo",
+            "us ", "ers", "ign", " ch", "lle", " ma", "rre", "ées", " va", "ide", "omm", "ten",
+            "abl", "act", "imp", "ec ", " av", "cha", " mo", "aut", " ta", " qu", "me ", " fo",
+            "tte", "cat", "nde", "sib", "ert", "ar ", "oss", "is ", " ou", "ort", "ale", "ure",
+            " ce", " éc", "êtr", "ica", "non", "nne", " lo", "rti", " ar", " op", " ve", "rée",
+            "ntr", " si", " et", "peu", "sta", "int", "and", "err", "ave", "tra", "ind", "nti",
+            "té ", " êt", "pro", "ir ", "ive", "nce", "ouv", "sup", "déf", "au ", "he ", "man",
+            "ini", "per", "ren", " di", "omp", "don", "teu", "née", "nné", "ite", " sy", "eut",
+            "sse", "pe ", "ie ", "ez ", "air", "ran", "sec", "ou ", "oir", "ill", "urs", "om ",
+            "pti", "anc", "iti", "ate", "fin", "ste", " ac", "isa", "upp", "cor", "vec", "arg",
+            "pri", " af", "tab", "ffi", "ien", " cl", " ca", "tur", "lid", "por", "aff", " er",
+            "uve", "oit", "sur", "for", "rat", "typ", "leu", "rec", "ype", "ous", "inc", " pl",
+            "rai", "ces", "att", "end", "mod", "ett", " ty", "nco", "son", "tes", " ét", "mat",
+            "opt", "reu", "tif", "lig", "gne", " ap", "éch", "ara", "her", "enc", "tai", "és ",
+            "pre", "tie", "at ", "orm", "rép", "str", "tro", "rou", "ode", "ins", " te", "tan",
+            "ace", " pu", " ba", "mme", "ell", "isé", " ob", "ais", "app", "sat", "lon", "tou",
+            "orr", "in ", "ors", "min", "éfi", "ule", "sou", "ére", "jou", " at", "al ", "nts",
+            "ssa", "si ", "ux ", "doi", "out", "dif", "éri", "rma", "tat", "rch", "erv", "ail",
+            "tiv", " st", " to", "inv", "pu ", "qui", "épe", "écu", "onf", "esp", " cr", "dre",
+            " vo", "rsi", "exi", "bas", "cri", "ctu", "aqu", "ère", "uet", " sa", "pon", "nda",
+            "rer", "art", "cte", " al", "bre", "ond", "lus", "ve ", "cal", "erm", "ndi", "nu ",
+            "veu", "ine", "lor", "toi", "nva", "tré", "onc", "san", "nst", " an", "ute", "loc",
+            " me", "emp", "sym", "ase", "pla", "éci", "pli", "nnu", "auc", "mma", "il ", "cré",
+            "fon", "rem", "fér", "eau", "sag", "nit", "mbo", "ext", "lic", "ets", "cod", "jet",
+            "ait", "ass", "rto", "all", " gi", "ole", "bol", "hec", "ymb", "lie", "pér", "seu",
+            "cou", "nal", "lat", "lem", "lec", "rge", "rac", "col", " sp", "arc", "ppr", "dis",
+            "uto", " b ", "rop", "ris", "mpl", "git", "ina", "spo", "nat", " vi", "ume", "ori",
+            "ité", "el ", "obj", "rve", "nes", "ram", "tem", "pré", "bje", "sé ", "ime", "cer",
+            "dex", "éra", "nct", "éta", "plu", "tiq", "spé", " fa", "ens", "paq", "fau", "oca",
+            "rit", "écr", "den", "oin", "péc", "nfi", " bi", "adr", " jo", " sé", "mai", "rim",
+            "sig", "uis", "mbr", " gr", "rés", "mis", "cun", "dép", "exp", "har", "ucu", "ex ",
+            "cif", "car", "mit", "ppo", "ls ", "sée", " id", "cet", "inf", " mi", "gra", "ura",
+            "ues", "equ", "sor", "exé", "été", "arr", "mer", "aux",
+        ],
+    ),
+    (
+        Language::ES,
+        &[
+            " de", "de ", "el ", " no", "do ", " se", "no ", " co", " el", "ón ", "ión", "os ",
+            " la", " es", "es ", "la ", " en", "se ", "ció", " re", "ar ", "en ", "ent", "con",
+            "ado", "ra ", " pa", " un", "or ", " in", "par", "as ", "est", "te ", "to ", "nte",
+            "aci", " pu", "da ", "ara", "ica", "al ", "ro ", "er ", "que", "tra", "na ", "ido",
+            "per", "ta ", "fic", "un ", "era", "sta", "ion", "com", "ist", "ero", "res", "str",
+            "ede", "cio", "men", " ar", "ada", "des", "ien", "rec", " ca", "ued", "pue", "del",
+            " pr", "esp", "nto", " si", " di", "on ", " lo", "cci", "ndo", "one", " al", "por",
+            "ivo", "nes", "re ", "arc", "ida", "ue ", "rad", " po", "and", " a ", "ntr", "lid",
+            "ter", "tos", "den", "io ", "una", " ti", " fi", "los", "ene", "vo ", "rch", "lo ",
+            "ten", " op", " qu", "ect", "nci", " ex", "rio", "ser", "ont", "chi", "enc", "hiv",
+            "tro", "ble", "ali", "las", "car", "bre", "esc", " so", "che", "spe", "abl", "ste",
+            "pro", "mit", " ta", " us", "dos", "cad", "mbr", " va", "dor", "ran", "po ", "omb",
+            "ifi", "ina", " ha", "ecc", "ato", "her", "sió", "reg", "nom", "tad", "stá", "ma ",
+            " fu", "ici", "áli", "vál", "ura", "cia", "tor", "cac", "err", "dir", "ir ", " ac",
+            "ori", "le ", "ich", "pre", " y ", "act", " fa", "ver", "ant", "iza", "tip", "tab",
+            " pe", "tar", "ndi", "rar", "omp", " su", "ia ", " ma", "deb", "ce ", " mo", "ire",
+            "udo", "liz", " er", "lic", "for", " o ", "cto", " ve", "ipo", "tá ", "tie", "pud",
+            "so ", "rma", "int", "ne ", "dat", "ona", "val", "ere", " te", "ari", "mie", "rac",
+            "egi", " da", "tes", " ob", "ebe", "sec", "ama", "les", "it ", "ini", "mo ", "dic",
+            "fal", "lor", "cid", "eci", "ca ", "all", "ces", "rro", "tiv", "min", "orm", "ite",
+            "pci", "ecu", "rea", "qui", " me", "nta", "pos", "olo", "fin", " tr", "rmi", "ror",
+            "erm", "alo", "nti", "ema", "usa", "rta", "cer", "inc", "nal", "ctu", "be ", "ual",
+            "arg", " li", "nst", "ins", "def", "nco", "opc", "ope", "go ", "ami", "ea ", "eta",
+            "ort", "ece", "gis", "end", "nea", "ner", "ace", "ete", "mer", "ers", "lec", "emp",
+            "mpo", "ono", "scr", "eto", "inv", "in ", "sin", " an", "pec", "uet", " fo", "nar",
+            " lí", "cri", "iva", "igu", "ase", " le", "git", "dad", "ext", "col", "cam", " vá",
+            "ord", " ra", "tic", "das", "esi", "bla", "tam", " ba", "efi", "ico", "nic", " sa",
+            "amb", "rde", "imi", "uta", "ore", "bol", "tan", "rsi", "co ", "noc", "ple", "gen",
+            "ume", "ros", "cre", "onf", "exi", "odo", "mpl", "art", "lla", "tua", "tru", "oci",
+            "ren", "ibl", "fue", "aba", " cu", "ubi", "ele", " cr", "cif", "ine", "dis", "cti",
+            "ad ", "sar", "ili", "ará", "unc", "ena", "uer", "ice", "mbi", "bic", " or", "obj",
+            "jet", "ier", "nde", " gi", "bje", " nu", "si ", "ost", "mod", "bas", "sal", " ad",
+            "tal", " im", "lta", "sol", "nvá", " má", "omo", "rib", "eso", "ria", " bi", " ap",
+            "jo ", "ens", "lar", "ert", "equ", "lis", "til", " nú", "ras", "osi", "rab", "ind",
+            "ale", "tur", "nad", "cor", "ram", "erv", "olu", "an ", "eri", "sco", "cla", "fun",
+            " au", "núm", " ej", "oce", "dif", "exp", "oca", "ing", "ons", " mu", "aqu", "ita",
+            "ref", "eje", "zad", "xis", "mbo", "mac", "tri", "tem", "zar", "úme", "iad", "lem",
+            "tec", "sca", "uie", "mas", "lín", "ult", "fer", "íne", "opo", "pla", "va ", "rti",
+            "orr", "pli", "uar", "nsa", "ha ", "rep", " ab", "pri", " mi", " sí", "ño ", "ló ",
+            "sti", "paq", "ios", "jec", "ati", "unt", "vis", "ade", "tid", "ora", "imp", " ni",
+            "lló", "eme", "alt", "lac", "nid", "eli", "edi", "nfi", "iti", "seg", "spa", "reu",
+            "sím", " cl", "gra", "ímb", "lim", " to", "gur", "ega",
+        ],
+    ),
+];
+
+/// A detected language together with how sure the detector is about it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Detection {
+    pub language: Language,
+    /// Between `0.0` (indistinguishable from the runner-up) and `1.0` (no other
+    /// language matched at all).
+    pub confidence: f64,
+}
+
+/// Offline, trigram-based language detector for short texts such as item names.
+///
+/// Each language is described by a ranked profile of its most frequent trigrams;
+/// a text scores for a language by every trigram it shares with the profile. Top
+/// ranks weigh up to twice as much as the last one, and a trigram found in several
+/// profiles is split among them, so distinctive trigrams decide.
+#[derive(Clone, Debug)]
+pub struct LanguageDetector {
+    profiles: Vec<(Language, HashMap<&'static str, f64>)>,
+}
+
+impl Default for LanguageDetector {
+    fn default() -> Self {
+        LanguageDetector::new(&[Language::DE, Language::EN, Language::FR, Language::ES])
+    }
+}
+
+impl LanguageDetector {
+    /// Detector restricted to `candidates`.
+    pub fn new(candidates: &[Language]) -> Self {
+        let mut shared_by: HashMap<&str, usize> = HashMap::new();
+        for (_, trigrams) in &PROFILES {
+            for trigram in *trigrams {
+                *shared_by.entry(trigram).or_default() += 1;
+            }
+        }
+        let profiles = PROFILES
+            .iter()
+            .filter(|(language, _)| candidates.contains(language))
+            .map(|(language, trigrams)| {
+                let weights = trigrams
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, trigram)| {
+                        let rank_weight = 2.0 - rank as f64 / trigrams.len() as f64;
+                        (*trigram, rank_weight / shared_by[trigram] as f64)
+                    })
+                    .collect();
+                (*language, weights)
+            })
+            .collect();
+        LanguageDetector { profiles }
+    }
+
+    /// Most likely language of `text`; `None` if no trigram matched any profile.
+    pub fn detect(&self, text: &str) -> Option<Detection> {
+        let mut scores = self.scores(text);
+        scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let (language, best) = *scores.first()?;
+        if best <= 0.0 {
+            return None;
+        }
+        let runner_up = scores.get(1).map_or(0.0, |(_, score)| *score);
+        Some(Detection {
+            language,
+            confidence: (best - runner_up) / best,
+        })
+    }
+
+    /// Builds a [`LocalizedText`] holding `text` as the original in its detected
+    /// language, or `None` if detection was less confident than `min_confidence`.
+    pub fn localize(&self, text: String, min_confidence: f64) -> Option<LocalizedText> {
+        let detection = self.detect(&text)?;
+        if detection.confidence < min_confidence {
+            return None;
+        }
+        Some(LocalizedText::original(detection.language, text))
+    }
+
+    fn scores(&self, text: &str) -> Vec<(Language, f64)> {
+        let trigrams = trigrams(text);
+        self.profiles
+            .iter()
+            .map(|(language, weights)| {
+                let score = trigrams
+                    .iter()
+                    .filter_map(|trigram| weights.get(trigram.as_str()))
+                    .sum();
+                (*language, score)
+            })
+            .collect()
+    }
+}
+
+/// Lower-cased trigrams of every word of `text`, each word padded with spaces.
+fn trigrams(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            let padded = format!(" {word} ").chars().collect::<Vec<_>>();
+            padded
+                .windows(3)
+                .map(|window| window.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::Language;
+    use crate::language::Language::{DE, EN, ES, FR};
+    use crate::language_detection::LanguageDetector;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Esstisch aus Eichenholz mit zwei Stühlen", DE)]
+    #[case("Die Kommode ist in einem sehr guten Zustand", DE)]
+    #[case("Solid oak dining table with two chairs", EN)]
+    #[case("The chest of drawers is in very good condition", EN)]
+    #[case("Table à manger en chêne avec deux chaises", FR)]
+    #[case("La commode est en très bon état", FR)]
+    #[case("Mesa de comedor de roble con dos sillas", ES)]
+    #[case("La cómoda está en muy buen estado para los años que tiene", ES)]
+    #[case("Vintage leather armchair", EN)]
+    #[case("Kleiderschrank weiß", DE)]
+    #[case("Canapé d'angle convertible", FR)]
+    #[case("Sofá cama de tres plazas", ES)]
+    // messages of catalogs held out from the profiles
+    #[case("Markierungen von dieser Datei importieren", DE)]
+    #[case("Anfrage hat keinen Bestimmungsort für die Ergebnisdaten", DE)]
+    #[case("Generate code for Freescale e500mc core complex", EN)]
+    #[case("Instruction address is not a multiple of four", EN)]
+    #[case("Permettre la relâche lors de l'édition de liens", FR)]
+    #[case("Impossible d'obtenir l'action du signal", FR)]
+    #[case("El calendario necesita un terminal", ES)]
+    #[case("No se puede obtener el identificador real", ES)]
+    fn should_detect_language(#[case] text: &str, #[case] expected: Language) {
+        let actual = LanguageDetector::default().detect(text).unwrap();
+
+        assert_eq!(actual.language, expected);
+        assert!(actual.confidence > 0.0);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1234 - 5678")]
+    fn should_not_detect_language_without_matching_trigrams(#[case] text: &str) {
+        assert_eq!(LanguageDetector::default().detect(text), None);
+    }
+
+    #[test]
+    fn should_only_detect_candidate_languages() {
+        let detector = LanguageDetector::new(&[EN, FR]);
+
+        let actual = detector.detect("Esstisch aus Eichenholz mit zwei Stühlen");
+
+        assert_ne!(actual.map(|detection| detection.language), Some(DE));
+    }
+
+    #[test]
+    fn should_localize_untagged_text_as_original() {
+        let detector = LanguageDetector::default();
+
+        let actual = detector
+            .localize("Solid oak dining table with two chairs".to_string(), 0.2)
+            .unwrap();
+
+        assert_eq!(actual.original_language(), Some(EN));
+        assert_eq!(
+            actual.get(&EN),
+            Some(&"Solid oak dining table with two chairs".to_string())
+        );
+    }
+
+    #[test]
+    fn should_not_localize_below_min_confidence() {
+        let detector = LanguageDetector::default();
+
+        assert_eq!(detector.localize("de la".to_string(), 0.9), None);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::language::Language;
+use crate::localized_text::LocalizedText;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// BCP 47 language tag of the form `language[-script][-region][-variant]*`,
+/// e.g. `de`, `de-AT`, `sr-Latn-RS` or `de-CH-1996`.
+///
+/// Subtags are stored in canonical case (`de`, `Latn`, `AT`), and the tag serializes
+/// as its string form, so plain tags like `de` are compatible with [`Language`].
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct LanguageTag {
+    language: String,
+    script: Option<String>,
+    region: Option<String>,
+    variants: Vec<String>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParseLanguageTagError {
+    Empty,
+    InvalidLanguage(String),
+    InvalidSubtag(String),
+}
+
+impl fmt::Display for ParseLanguageTagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseLanguageTagError::Empty => f.write_str("empty language tag"),
+            ParseLanguageTagError::InvalidLanguage(subtag) => {
+                write!(f, "invalid primary language subtag '{subtag}'")
+            }
+            ParseLanguageTagError::InvalidSubtag(subtag) => {
+                write!(f, "invalid language tag subtag '{subtag}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseLanguageTagError {}
+
+fn is_alpha(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_variant(s: &str) -> bool {
+    let alphanumeric = s.bytes().all(|b| b.is_ascii_alphanumeric());
+    let starts_with_digit = s.bytes().next().is_some_and(|b| b.is_ascii_digit());
+    alphanumeric && ((5..=8).contains(&s.len()) || (s.len() == 4 && starts_with_digit))
+}
+
+impl LanguageTag {
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn script(&self) -> Option<&str> {
+        self.script.as_deref()
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    pub fn variants(&self) -> &[String] {
+        &self.variants
+    }
+
+    /// The primary language as [`Language`], if it is one we support.
+    pub fn primary_language(&self) -> Option<Language> {
+        self.language.to_uppercase().parse().ok()
+    }
+
+    /// This tag and its successively truncated parents, most specific first,
+    /// e.g. `sr-Latn-RS`, `sr-Latn`, `sr`.
+    pub fn truncations(&self) -> Vec<LanguageTag> {
+        let mut chain = vec![self.clone()];
+        let mut current = self.clone();
+        loop {
+            if current.variants.pop().is_none()
+                && current.region.take().is_none()
+                && current.script.take().is_none()
+            {
+                break;
+            }
+            chain.push(current.clone());
+        }
+        chain
+    }
+}
+
+impl From<Language> for LanguageTag {
+    fn from(language: Language) -> Self {
+        LanguageTag {
+            language: language.to_string().to_lowercase(),
+            script: None,
+            region: None,
+            variants: Vec::new(),
+        }
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = ParseLanguageTagError;
+
+    /// Parses `de`, `de-AT`, `de_at`, `sr-Latn-RS` and the like; `_` is accepted as separator.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut subtags = s.split(['-', '_']).peekable();
+        let language = match subtags.next() {
+            Some("") | None => return Err(ParseLanguageTagError::Empty),
+            Some(language) if is_alpha(language, 2, 3) => language.to_ascii_lowercase(),
+            Some(language) => {
+                return Err(ParseLanguageTagError::InvalidLanguage(language.to_string()));
+            }
+        };
+        let script = subtags
+            .next_if(|subtag| is_alpha(subtag, 4, 4))
+            .map(|script| {
+                let (first, rest) = script.split_at(1);
+                first.to_ascii_uppercase() + &rest.to_ascii_lowercase()
+            });
+        let region = subtags
+            .next_if(|subtag| {
+                is_alpha(subtag, 2, 2)
+                    || (subtag.len() == 3 && subtag.bytes().all(|b| b.is_ascii_digit()))
+            })
+            .map(|region| region.to_ascii_uppercase());
+        let variants = subtags
+            .map(|subtag| {
+                if is_variant(subtag) {
+                    Ok(subtag.to_ascii_lowercase())
+                } else {
+                    Err(ParseLanguageTagError::InvalidSubtag(subtag.to_string()))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(LanguageTag {
+            language,
+            script,
+            region,
+            variants,
+        })
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.language)?;
+        for subtag in self
+            .script
+            .iter()
+            .chain(self.region.iter())
+            .chain(self.variants.iter())
+        {
+            write!(f, "-{subtag}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for LanguageTag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for LanguageTag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Picks the best entry of a [`LocalizedText`] for a requested tag by walking a fallback
+/// chain: the tag's truncations (`de-AT` → `de`), then the configured default languages.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LanguageResolver {
+    defaults: Vec<Language>,
+}
+
+impl Default for LanguageResolver {
+    fn default() -> Self {
+        LanguageResolver::new(vec![Language::EN])
+    }
+}
+
+impl LanguageResolver {
+    pub fn new(defaults: Vec<Language>) -> Self {
+        LanguageResolver { defaults }
+    }
+
+    /// Languages to try for `requested`, most preferred first and without duplicates.
+    pub fn fallback_chain(&self, requested: &LanguageTag) -> Vec<Language> {
+        let mut chain: Vec<Language> = Vec::new();
+        let candidates = requested
+            .truncations()
+            .iter()
+            .filter_map(LanguageTag::primary_language)
+            .chain(self.defaults.iter().copied())
+            .collect::<Vec<_>>();
+        for language in candidates {
+            if !chain.contains(&language) {
+                chain.push(language);
+            }
+        }
+        chain
+    }
+
+    /// Best entry of `text` for `requested`, together with the language it was found in.
+    pub fn resolve<'a>(
+        &self,
+        requested: &LanguageTag,
+        text: &'a LocalizedText,
+    ) -> Option<(Language, &'a String)> {
+        self.fallback_chain(requested)
+            .into_iter()
+            .find_map(|language| text.get(&language).map(|entry| (language, entry)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::Language;
+    use crate::language::Language::{DE, EN, FR};
+    use crate::language_tag::{LanguageResolver, LanguageTag, ParseLanguageTagError};
+    use crate::localized_text::LocalizedText;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("de", "de")]
+    #[case("DE", "de")]
+    #[case("de-at", "de-AT")]
+    #[case("de_AT", "de-AT")]
+    #[case("sr-latn-rs", "sr-Latn-RS")]
+    #[case("es-419", "es-419")]
+    #[case("de-CH-1996", "de-CH-1996")]
+    fn should_parse_and_canonicalize_tag(#[case] input: &str, #[case] expected: &str) {
+        let actual = input.parse::<LanguageTag>().unwrap();
+        assert_eq!(actual.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("", ParseLanguageTagError::Empty)]
+    #[case("deutsch", ParseLanguageTagError::InvalidLanguage("deutsch".to_string()))]
+    #[case("de-A", ParseLanguageTagError::InvalidSubtag("A".to_string()))]
+    fn should_reject_invalid_tag(#[case] input: &str, #[case] expected: ParseLanguageTagError) {
+        let actual = input.parse::<LanguageTag>().unwrap_err();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(Language::DE)]
+    #[case(Language::EN)]
+    #[case(Language::FR)]
+    #[case(Language::ES)]
+    fn should_serialize_compatibly_with_language(#[case] language: Language) {
+        let tag = LanguageTag::from(language);
+
+        let serialized = serde_json::to_string(&tag).unwrap();
+
+        assert_eq!(serialized, serde_json::to_string(&language).unwrap());
+        assert_eq!(
+            serde_json::from_str::<Language>(&serialized).unwrap(),
+            language
+        );
+        assert_eq!(
+            serde_json::from_str::<LanguageTag>(&serialized).unwrap(),
+            tag
+        );
+    }
+
+    #[test]
+    fn should_truncate_most_specific_first() {
+        let tag = "sr-Latn-RS".parse::<LanguageTag>().unwrap();
+
+        let actual: Vec<String> = tag.truncations().iter().map(|t| t.to_string()).collect();
+
+        assert_eq!(actual, vec!["sr-Latn-RS", "sr-Latn", "sr"]);
+    }
+
+    #[test]
+    fn should_walk_fallback_chain() {
+        let resolver = LanguageResolver::default();
+        let text = LocalizedText::from([(EN, "bar".to_string()), (FR, "barre".to_string())]);
+
+        let de_at = "de-AT".parse().unwrap();
+        let fr_ch = "fr-CH".parse().unwrap();
+
+        assert_eq!(resolver.fallback_chain(&de_at), vec![DE, EN]);
+        assert_eq!(
+            resolver.resolve(&de_at, &text),
+            Some((EN, &"bar".to_string()))
+        );
+        assert_eq!(
+            resolver.resolve(&fr_ch, &text),
+            Some((FR, &"barre".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_prefer_requested_language_over_default() {
+        let resolver = LanguageResolver::new(vec![EN, FR]);
+        let text = LocalizedText::from([(DE, "balken".to_string()), (EN, "bar".to_string())]);
+
+        let actual = resolver.resolve(&"de-CH".parse().unwrap(), &text);
+
+        assert_eq!(actual, Some((DE, &"balken".to_string())));
+    }
+
+    #[test]
+    fn should_resolve_nothing_without_matching_entry() {
+        let resolver = LanguageResolver::new(vec![]);
+        let text = LocalizedText::from([(DE, "balken".to_string())]);
+
+        assert_eq!(resolver.resolve(&"ja-JP".parse().unwrap(), &text), None);
+    }
+}
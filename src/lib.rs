@@ -1,7 +1,29 @@
+pub mod clock;
+pub mod currency;
 pub mod ddb_prefix;
+pub mod error;
+pub mod exchange_rate;
+pub mod item_aggregate;
+pub mod item_changelog;
 pub mod item_data;
+pub mod item_digest;
 pub mod item_hash;
+pub mod item_lifecycle;
 pub mod item_model;
+pub mod item_snapshot;
 pub mod item_state;
 pub mod language;
+pub mod language_detection;
+pub mod language_tag;
+pub mod localized_text;
+pub mod money;
+pub mod patch;
 pub mod price;
+pub mod price_locale;
+pub mod price_range;
+pub mod timestamp;
+
+#[cfg(test)]
+mod test_support;
+
+pub use error::Error;
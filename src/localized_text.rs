@@ -0,0 +1,383 @@
+use crate::language::Language;
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// Text in several languages, iterated in `Language` declaration order.
+///
+/// Besides the texts it records which language is the original one and which
+/// texts were machine-translated. Serializes as the plain `{"en": "...", "de": "..."}`
+/// map without provenance; a struct keeps provenance next to the map with the
+/// `ser_flat_*`/`de_flat_*` functions below.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct LocalizedText {
+    texts: BTreeMap<Language, String>,
+    original: Option<Language>,
+    machine_translated: BTreeSet<Language>,
+}
+
+impl LocalizedText {
+    pub fn new() -> Self {
+        LocalizedText::default()
+    }
+
+    /// Text whose only entry is the original in `lang`.
+    pub fn original(lang: Language, text: String) -> Self {
+        let mut localized = LocalizedText::new();
+        localized.insert_original(lang, text);
+        localized
+    }
+
+    /// Inserts a text without provenance, replacing any previous text and provenance for `lang`.
+    pub fn insert(&mut self, lang: Language, text: String) -> Option<String> {
+        self.machine_translated.remove(&lang);
+        if self.original == Some(lang) {
+            self.original = None;
+        }
+        self.texts.insert(lang, text)
+    }
+
+    /// Inserts `text` as the original, replacing any previously marked original language.
+    pub fn insert_original(&mut self, lang: Language, text: String) -> Option<String> {
+        let previous = self.insert(lang, text);
+        self.original = Some(lang);
+        previous
+    }
+
+    pub fn insert_machine_translated(&mut self, lang: Language, text: String) -> Option<String> {
+        let previous = self.insert(lang, text);
+        self.machine_translated.insert(lang);
+        previous
+    }
+
+    pub fn remove(&mut self, lang: Language) -> Option<String> {
+        self.machine_translated.remove(&lang);
+        if self.original == Some(lang) {
+            self.original = None;
+        }
+        self.texts.remove(&lang)
+    }
+
+    pub fn get(&self, lang: &Language) -> Option<&String> {
+        self.texts.get(lang)
+    }
+
+    pub fn contains(&self, lang: &Language) -> bool {
+        self.texts.contains_key(lang)
+    }
+
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Language of the original text, if known.
+    pub fn original_language(&self) -> Option<Language> {
+        self.original
+    }
+
+    pub fn original_text(&self) -> Option<&String> {
+        self.texts.get(&self.original?)
+    }
+
+    pub fn is_machine_translated(&self, lang: Language) -> bool {
+        self.machine_translated.contains(&lang)
+    }
+
+    /// Languages whose texts were machine-translated, in stable order.
+    pub fn machine_translated(&self) -> impl Iterator<Item = Language> + '_ {
+        self.machine_translated.iter().copied()
+    }
+
+    /// Marks which language is the original, e.g. after restoring from storage.
+    /// Ignored unless a text in `lang` exists.
+    pub fn set_original_language(&mut self, lang: Language) {
+        if self.texts.contains_key(&lang) {
+            self.machine_translated.remove(&lang);
+            self.original = Some(lang);
+        }
+    }
+
+    /// Marks the existing text in `lang` as machine-translated.
+    pub fn set_machine_translated(&mut self, lang: Language) {
+        if self.texts.contains_key(&lang) && self.original != Some(lang) {
+            self.machine_translated.insert(lang);
+        }
+    }
+
+    /// Texts in stable `Language` order.
+    pub fn iter(&self) -> impl Iterator<Item = (Language, &String)> {
+        self.texts.iter().map(|(lang, text)| (*lang, text))
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = Language> + '_ {
+        self.texts.keys().copied()
+    }
+
+    /// Best text for a reader preferring `preferred` (most preferred first): the first
+    /// preferred language present, else the original, else any text that was not
+    /// machine-translated, else any text at all.
+    pub fn best_for(&self, preferred: &[Language]) -> Option<(Language, &String)> {
+        preferred
+            .iter()
+            .find_map(|lang| self.texts.get(lang).map(|text| (*lang, text)))
+            .or_else(|| {
+                let original = self.original?;
+                self.texts.get(&original).map(|text| (original, text))
+            })
+            .or_else(|| {
+                self.iter()
+                    .find(|(lang, _)| !self.machine_translated.contains(lang))
+            })
+            .or_else(|| self.iter().next())
+    }
+}
+
+impl FromIterator<(Language, String)> for LocalizedText {
+    fn from_iter<T: IntoIterator<Item = (Language, String)>>(iter: T) -> Self {
+        LocalizedText {
+            texts: iter.into_iter().collect(),
+            original: None,
+            machine_translated: BTreeSet::new(),
+        }
+    }
+}
+
+impl<const N: usize> From<[(Language, String); N]> for LocalizedText {
+    fn from(texts: [(Language, String); N]) -> Self {
+        texts.into_iter().collect()
+    }
+}
+
+impl From<HashMap<Language, String>> for LocalizedText {
+    fn from(texts: HashMap<Language, String>) -> Self {
+        texts.into_iter().collect()
+    }
+}
+
+impl IntoIterator for LocalizedText {
+    type Item = (Language, String);
+    type IntoIter = std::collections::btree_map::IntoIter<Language, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.texts.into_iter()
+    }
+}
+
+impl Serialize for LocalizedText {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.texts.len()))?;
+        for (lang, text) in &self.texts {
+            map.serialize_entry(lang, text)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedText {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let texts = BTreeMap::deserialize(deserializer)?;
+        Ok(LocalizedText {
+            texts,
+            ..LocalizedText::default()
+        })
+    }
+}
+
+/// Keys of a flattened [`LocalizedText`]: the texts, the original language and the
+/// machine-translated languages.
+type FlatKeys = [&'static str; 3];
+
+impl LocalizedText {
+    fn serialize_flat<S>(&self, keys: &FlatKeys, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let [texts_key, original_key, machine_translated_key] = *keys;
+        let mut map = serializer.serialize_map(None)?;
+        if !self.is_empty() {
+            map.serialize_entry(texts_key, self)?;
+        }
+        if let Some(original) = &self.original {
+            map.serialize_entry(original_key, original)?;
+        }
+        if !self.machine_translated.is_empty() {
+            map.serialize_entry(machine_translated_key, &self.machine_translated)?;
+        }
+        map.end()
+    }
+
+    fn deserialize_flat<'de, D>(keys: &'static FlatKeys, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FlatVisitor(&'static FlatKeys);
+
+        impl<'de> Visitor<'de> for FlatVisitor {
+            type Value = LocalizedText;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "texts under '{}' with their provenance", self.0[0])
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let [texts_key, original_key, machine_translated_key] = *self.0;
+                let mut localized = LocalizedText::new();
+                let mut original: Option<Language> = None;
+                let mut machine_translated: Vec<Language> = Vec::new();
+                while let Some(key) = access.next_key::<String>()? {
+                    if key == texts_key {
+                        localized = access.next_value()?;
+                    } else if key == original_key {
+                        original = access.next_value()?;
+                    } else if key == machine_translated_key {
+                        machine_translated = access.next_value()?;
+                    } else {
+                        access.next_value::<IgnoredAny>()?;
+                    }
+                }
+                for lang in machine_translated {
+                    localized.set_machine_translated(lang);
+                }
+                if let Some(original) = original {
+                    localized.set_original_language(original);
+                }
+                Ok(localized)
+            }
+        }
+
+        deserializer.deserialize_struct("LocalizedText", keys, FlatVisitor(keys))
+    }
+}
+
+/// Generates serde functions for a `#[serde(flatten)]` [`LocalizedText`] field,
+/// storing the plain map under `texts` and the provenance next to it.
+macro_rules! make_flat_fns {
+    ($ser:ident, $de:ident, texts = $texts:literal, original = $original:literal, machine_translated = $machine_translated:literal) => {
+        pub fn $ser<S>(text: &LocalizedText, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            text.serialize_flat(&[$texts, $original, $machine_translated], serializer)
+        }
+
+        pub fn $de<'de, D>(deserializer: D) -> Result<LocalizedText, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            LocalizedText::deserialize_flat(&[$texts, $original, $machine_translated], deserializer)
+        }
+    };
+}
+
+make_flat_fns!(
+    ser_flat_name,
+    de_flat_name,
+    texts = "name",
+    original = "nameOriginal",
+    machine_translated = "nameMachineTranslated"
+);
+make_flat_fns!(
+    ser_flat_description,
+    de_flat_description,
+    texts = "description",
+    original = "descriptionOriginal",
+    machine_translated = "descriptionMachineTranslated"
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::language::Language::{DE, EN, ES, FR};
+    use crate::localized_text::LocalizedText;
+
+    #[test]
+    fn should_deserialize_legacy_map() {
+        let json = r#"{"en":"bar","de":"balken"}"#;
+        let expected = LocalizedText::from([(EN, "bar".to_string()), (DE, "balken".to_string())]);
+
+        let actual = serde_json::from_str::<LocalizedText>(json).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.original_language(), None);
+    }
+
+    #[test]
+    fn should_serialize_in_stable_order_as_plain_map() {
+        let text = LocalizedText::from([
+            (ES, "barra".to_string()),
+            (EN, "bar".to_string()),
+            (FR, "barre".to_string()),
+            (DE, "balken".to_string()),
+        ]);
+
+        let actual = serde_json::to_string(&text).unwrap();
+
+        assert_eq!(
+            actual,
+            r#"{"de":"balken","en":"bar","fr":"barre","es":"barra"}"#
+        );
+    }
+
+    #[test]
+    fn should_serialize_without_provenance() {
+        let mut text = LocalizedText::original(DE, "balken".to_string());
+        text.insert_machine_translated(EN, "bar".to_string());
+
+        let actual = serde_json::to_string(&text).unwrap();
+
+        assert_eq!(actual, r#"{"de":"balken","en":"bar"}"#);
+    }
+
+    #[test]
+    fn should_reject_unknown_language_key() {
+        let json = r#"{"xx":"bar"}"#;
+        assert!(serde_json::from_str::<LocalizedText>(json).is_err());
+    }
+
+    #[test]
+    fn should_clear_provenance_when_overwritten() {
+        let mut text = LocalizedText::original(DE, "balken".to_string());
+        text.insert_machine_translated(EN, "bar".to_string());
+
+        text.insert(DE, "Balken".to_string());
+        text.insert(EN, "beam".to_string());
+
+        assert_eq!(text.original_language(), None);
+        assert!(!text.is_machine_translated(EN));
+    }
+
+    #[test]
+    fn should_pick_best_text_for_preferences() {
+        let mut text = LocalizedText::new();
+        text.insert_machine_translated(EN, "bar".to_string());
+        text.insert_original(FR, "barre".to_string());
+        text.insert(ES, "barra".to_string());
+
+        assert_eq!(text.best_for(&[DE, EN]), Some((EN, &"bar".to_string())));
+        assert_eq!(text.best_for(&[DE]), Some((FR, &"barre".to_string())));
+        assert_eq!(LocalizedText::new().best_for(&[DE]), None);
+    }
+
+    #[test]
+    fn should_prefer_human_text_without_original() {
+        let mut text = LocalizedText::new();
+        text.insert_machine_translated(DE, "balken".to_string());
+        text.insert(ES, "barra".to_string());
+
+        assert_eq!(text.best_for(&[]), Some((ES, &"barra".to_string())));
+    }
+}
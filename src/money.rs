@@ -0,0 +1,634 @@
+use crate::price::Currency;
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Maximum number of fractional digits a [`Money`] may carry.
+pub const MAX_SCALE: u8 = 18;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum RoundingMode {
+    /// Away from zero.
+    Up,
+    /// Towards zero (truncate).
+    Down,
+    /// Towards positive infinity.
+    Ceiling,
+    /// Towards negative infinity.
+    Floor,
+    /// To nearest, ties away from zero.
+    HalfUp,
+    /// To nearest, ties towards zero.
+    HalfDown,
+    /// To nearest, ties to the even neighbour (banker's rounding).
+    HalfEven,
+}
+
+pub const DEFAULT_ROUNDING_MODE: RoundingMode = RoundingMode::HalfEven;
+
+/// Exact decimal amount: `mantissa * 10^-scale`.
+///
+/// Equality, ordering and hashing are numeric, so `42`, `42.0` and `42.00` are equal.
+/// Serializes as its exact decimal string, scale included (e.g. `"42.00"`), and
+/// deserializes from such strings or from numbers (e.g. legacy `42.0`).
+#[derive(Copy, Clone, Debug)]
+pub struct Money {
+    mantissa: i64,
+    scale: u8,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParseMoneyError {
+    Empty,
+    InvalidDigit,
+    ScaleTooLarge,
+    Overflow,
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoneyError::Empty => f.write_str("cannot parse money from empty string"),
+            ParseMoneyError::InvalidDigit => f.write_str("invalid digit found in money string"),
+            ParseMoneyError::ScaleTooLarge => {
+                write!(f, "money supports at most {MAX_SCALE} fractional digits")
+            }
+            ParseMoneyError::Overflow => f.write_str("money amount too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+fn pow10(exp: u8) -> i128 {
+    10i128.pow(exp as u32)
+}
+
+/// Divides `value` by `10^exp`, rounding the discarded digits according to `mode`.
+fn div_pow10_rounded(value: i128, exp: u8, mode: RoundingMode) -> i128 {
+    div_rounded(value, pow10(exp), mode)
+}
+
+/// Divides `value` by `divisor` (non-zero), rounding the remainder according to `mode`.
+fn div_rounded(value: i128, divisor: i128, mode: RoundingMode) -> i128 {
+    let quotient = value / divisor;
+    let remainder = value % divisor;
+    if remainder == 0 {
+        return quotient;
+    }
+    let sign = value.signum() * divisor.signum();
+    let away = quotient + sign;
+    let twice = remainder.abs() * 2;
+    let divisor = divisor.abs();
+    let round_away = match mode {
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => sign > 0,
+        RoundingMode::Floor => sign < 0,
+        RoundingMode::HalfUp => twice >= divisor,
+        RoundingMode::HalfDown => twice > divisor,
+        RoundingMode::HalfEven => twice > divisor || (twice == divisor && quotient % 2 != 0),
+    };
+    if round_away { away } else { quotient }
+}
+
+impl Money {
+    pub const ZERO: Money = Money {
+        mantissa: 0,
+        scale: 0,
+    };
+
+    /// `mantissa * 10^-scale`, e.g. `Money::new(1999, 2)` is `19.99`.
+    ///
+    /// # Panics
+    ///
+    /// If `scale` exceeds [`MAX_SCALE`]. Use [`Money::try_new`] for scales from input.
+    pub fn new(mantissa: i64, scale: u8) -> Self {
+        Money::try_new(mantissa, scale)
+            .unwrap_or_else(|| panic!("scale must not exceed {MAX_SCALE}"))
+    }
+
+    /// `mantissa * 10^-scale`, or `None` if `scale` exceeds [`MAX_SCALE`].
+    pub fn try_new(mantissa: i64, scale: u8) -> Option<Self> {
+        (scale <= MAX_SCALE).then_some(Money { mantissa, scale })
+    }
+
+    pub fn from_major(units: i64) -> Self {
+        Money::new(units, 0)
+    }
+
+    /// Amount from the smallest unit of the currency, e.g. cents for EUR.
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Self {
+        Money::new(minor_units, currency.minor_unit_exponent())
+    }
+
+    /// Converts a float via its shortest round-trip representation, so `19.99f64` becomes
+    /// exactly `19.99`. Returns `None` for non-finite values or values out of range.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        value.to_string().parse().ok()
+    }
+
+    pub fn mantissa(&self) -> i64 {
+        self.mantissa
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Amount in the smallest unit of `currency`, rounded with `mode`.
+    pub fn minor_units(&self, currency: Currency, mode: RoundingMode) -> Option<i64> {
+        self.round(currency.minor_unit_exponent(), mode)
+            .map(|money| money.mantissa)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        // Formatting and parsing again gives a correctly rounded result,
+        // unlike dividing by a power of ten.
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Same value without trailing fractional zeros, e.g. `42.50` becomes `42.5`.
+    pub fn normalized(&self) -> Self {
+        let mut mantissa = self.mantissa;
+        let mut scale = self.scale;
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Money { mantissa, scale }
+    }
+
+    /// Rounds (or pads) to exactly `scale` fractional digits.
+    pub fn round(&self, scale: u8, mode: RoundingMode) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        let mantissa = if scale >= self.scale {
+            (self.mantissa as i128).checked_mul(pow10(scale - self.scale))?
+        } else {
+            div_pow10_rounded(self.mantissa as i128, self.scale - scale, mode)
+        };
+        Some(Money {
+            mantissa: i64::try_from(mantissa).ok()?,
+            scale,
+        })
+    }
+
+    /// Rounds to the minor-unit exponent of `currency`.
+    pub fn round_to(&self, currency: Currency, mode: RoundingMode) -> Option<Self> {
+        self.round(currency.minor_unit_exponent(), mode)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Money {
+            mantissa: self.mantissa.checked_neg()?,
+            scale: self.scale,
+        })
+    }
+
+    pub fn checked_add(&self, other: Money) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let lhs = self.round(scale, RoundingMode::Down)?;
+        let rhs = other.round(scale, RoundingMode::Down)?;
+        Some(Money {
+            mantissa: lhs.mantissa.checked_add(rhs.mantissa)?,
+            scale,
+        })
+    }
+
+    pub fn checked_sub(&self, other: Money) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    /// Exact product with the sum of both scales, unless that exceeds [`MAX_SCALE`],
+    /// in which case it is rounded with [`DEFAULT_ROUNDING_MODE`].
+    /// Use [`Money::round`] to bring it back to the desired precision.
+    pub fn checked_mul(&self, other: Money) -> Option<Self> {
+        let scale = self.scale.checked_add(other.scale)?;
+        let mantissa = (self.mantissa as i128).checked_mul(other.mantissa as i128)?;
+        if scale <= MAX_SCALE {
+            return Some(Money {
+                mantissa: i64::try_from(mantissa).ok()?,
+                scale,
+            });
+        }
+        let mantissa = div_pow10_rounded(mantissa, scale - MAX_SCALE, DEFAULT_ROUNDING_MODE);
+        Some(Money {
+            mantissa: i64::try_from(mantissa).ok()?,
+            scale: MAX_SCALE,
+        })
+    }
+
+    /// Product rounded to `scale` fractional digits with `mode`.
+    pub fn checked_mul_rounded(&self, other: Money, scale: u8, mode: RoundingMode) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        let exact_scale = self.scale as u16 + other.scale as u16;
+        let mantissa = (self.mantissa as i128).checked_mul(other.mantissa as i128)?;
+        let mantissa = if scale as u16 >= exact_scale {
+            mantissa.checked_mul(pow10(scale - exact_scale as u8))?
+        } else {
+            div_pow10_rounded(mantissa, (exact_scale - scale as u16) as u8, mode)
+        };
+        Some(Money {
+            mantissa: i64::try_from(mantissa).ok()?,
+            scale,
+        })
+    }
+
+    /// Quotient rounded to `scale` fractional digits with `mode`.
+    /// `None` on division by zero or overflow.
+    pub fn checked_div(&self, other: Money, scale: u8, mode: RoundingMode) -> Option<Self> {
+        if other.mantissa == 0 || scale > MAX_SCALE {
+            return None;
+        }
+        // self / other = (m1 / m2) * 10^(s2 - s1), shifted to `scale` digits
+        let shift = scale as i32 + other.scale as i32 - self.scale as i32;
+        let (numerator, divisor) = if shift >= 0 {
+            (
+                (self.mantissa as i128).checked_mul(10i128.checked_pow(shift as u32)?)?,
+                other.mantissa as i128,
+            )
+        } else {
+            (
+                self.mantissa as i128,
+                (other.mantissa as i128).checked_mul(10i128.checked_pow((-shift) as u32)?)?,
+            )
+        };
+        Some(Money {
+            mantissa: i64::try_from(div_rounded(numerator, divisor, mode)).ok()?,
+            scale,
+        })
+    }
+
+    /// Both mantissas rescaled to a common scale, for comparison.
+    fn aligned(&self, other: &Money) -> (i128, i128) {
+        let scale = self.scale.max(other.scale);
+        (
+            self.mantissa as i128 * pow10(scale - self.scale),
+            other.mantissa as i128 * pow10(scale - other.scale),
+        )
+    }
+}
+
+impl Default for Money {
+    fn default() -> Self {
+        Money::ZERO
+    }
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = self.aligned(other);
+        lhs == rhs
+    }
+}
+
+impl Eq for Money {}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Money {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (lhs, rhs) = self.aligned(other);
+        lhs.cmp(&rhs)
+    }
+}
+
+impl Hash for Money {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let abs = self.mantissa.unsigned_abs();
+        if self.scale == 0 {
+            return write!(f, "{sign}{abs}");
+        }
+        let divisor = 10u64.pow(self.scale as u32);
+        write!(
+            f,
+            "{sign}{}.{:0width$}",
+            abs / divisor,
+            abs % divisor,
+            width = self.scale as usize
+        )
+    }
+}
+
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    /// Parses plain decimal notation like `-1234.56`; no separators or exponents.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(ParseMoneyError::Empty);
+        }
+        if !int_part
+            .bytes()
+            .chain(frac_part.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return Err(ParseMoneyError::InvalidDigit);
+        }
+        // keeps the scale, unless trailing zeros make it too large or overflow
+        parse_digits(negative, int_part, frac_part).or_else(|e| {
+            let trimmed = frac_part.trim_end_matches('0');
+            if trimmed.len() < frac_part.len() {
+                parse_digits(negative, int_part, trimmed)
+            } else {
+                Err(e)
+            }
+        })
+    }
+}
+
+/// Amount from its integer and fractional digits, accumulated with their sign so
+/// `i64::MIN` parses too.
+fn parse_digits(negative: bool, int_part: &str, frac_part: &str) -> Result<Money, ParseMoneyError> {
+    let scale = u8::try_from(frac_part.len()).map_err(|_| ParseMoneyError::ScaleTooLarge)?;
+    let mut mantissa: i64 = 0;
+    for b in int_part.bytes().chain(frac_part.bytes()) {
+        mantissa = mantissa
+            .checked_mul(10)
+            .and_then(|m| {
+                let digit = (b - b'0') as i64;
+                if negative {
+                    m.checked_sub(digit)
+                } else {
+                    m.checked_add(digit)
+                }
+            })
+            .ok_or(ParseMoneyError::Overflow)?;
+    }
+    Money::try_new(mantissa, scale).ok_or(ParseMoneyError::ScaleTooLarge)
+}
+
+impl Serialize for Money {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MoneyVisitor;
+
+        impl Visitor<'_> for MoneyVisitor {
+            type Value = Money;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a decimal number or decimal string")
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(Money::from_major(v))
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                i64::try_from(v)
+                    .map(Money::from_major)
+                    .map_err(|_| E::custom(ParseMoneyError::Overflow))
+            }
+
+            fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+                Money::from_f64(v).ok_or_else(|| E::custom(format!("invalid money amount {v}")))
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::money::{MAX_SCALE, Money, ParseMoneyError, RoundingMode};
+    use crate::price::Currency::EUR;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("19.99", Money::new(1999, 2))]
+    #[case("42", Money::from_major(42))]
+    #[case("42.000", Money::from_major(42))]
+    #[case("-0.5", Money::new(-5, 1))]
+    #[case(".5", Money::new(5, 1))]
+    fn should_parse_money_from_str(#[case] input: &str, #[case] expected: Money) {
+        let actual = input.parse::<Money>().unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", ParseMoneyError::Empty)]
+    #[case("1,5", ParseMoneyError::InvalidDigit)]
+    #[case("1e5", ParseMoneyError::InvalidDigit)]
+    #[case("99999999999999999999", ParseMoneyError::Overflow)]
+    #[case("0.0000000000000000001", ParseMoneyError::ScaleTooLarge)]
+    fn should_reject_invalid_money_str(#[case] input: &str, #[case] expected: ParseMoneyError) {
+        let actual = input.parse::<Money>().unwrap_err();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(Money::new(1999, 2), "19.99")]
+    #[case(Money::new(4200, 2), "42.00")]
+    #[case(Money::new(-5, 2), "-0.05")]
+    #[case(Money::from_major(42), "42")]
+    fn should_display_money_with_its_scale(#[case] money: Money, #[case] expected: &str) {
+        assert_eq!(money.to_string(), expected);
+    }
+
+    #[test]
+    fn should_deserialize_legacy_float_amount() {
+        let actual = serde_json::from_str::<Money>("42.0").unwrap();
+        assert_eq!(actual, Money::from_major(42));
+    }
+
+    #[test]
+    fn should_serialize_money_as_exact_decimal_string() {
+        let actual = serde_json::to_string(&Money::new(4200, 2)).unwrap();
+        assert_eq!(actual, r#""42.00""#);
+    }
+
+    #[rstest]
+    #[case(Money::new(1999, 2))]
+    #[case(Money::new(1, 18))]
+    #[case(Money::new(123456789012345, 3))]
+    #[case(Money::new(i64::MAX, 2))]
+    #[case(Money::new(i64::MIN, 18))]
+    fn should_round_trip_money_losslessly(#[case] money: Money) {
+        let serialized = serde_json::to_string(&money).unwrap();
+        let actual = serde_json::from_str::<Money>(&serialized).unwrap();
+        assert_eq!(actual.mantissa(), money.mantissa());
+        assert_eq!(actual.scale(), money.scale());
+    }
+
+    #[rstest]
+    #[case("42.00", 4200, 2)]
+    #[case("42", 42, 0)]
+    #[case("-0.50", -50, 2)]
+    #[case("42.000000000000000000", 42, 0)]
+    fn should_preserve_scale_when_parsing(
+        #[case] input: &str,
+        #[case] mantissa: i64,
+        #[case] scale: u8,
+    ) {
+        let actual = input.parse::<Money>().unwrap();
+        assert_eq!((actual.mantissa(), actual.scale()), (mantissa, scale));
+    }
+
+    #[rstest]
+    #[case(Money::new(125, 3), RoundingMode::HalfEven, Money::new(12, 2))]
+    #[case(Money::new(135, 3), RoundingMode::HalfEven, Money::new(14, 2))]
+    #[case(Money::new(125, 3), RoundingMode::HalfUp, Money::new(13, 2))]
+    #[case(Money::new(125, 3), RoundingMode::HalfDown, Money::new(12, 2))]
+    #[case(Money::new(-125, 3), RoundingMode::HalfUp, Money::new(-13, 2))]
+    #[case(Money::new(121, 3), RoundingMode::Up, Money::new(13, 2))]
+    #[case(Money::new(129, 3), RoundingMode::Down, Money::new(12, 2))]
+    #[case(Money::new(-121, 3), RoundingMode::Ceiling, Money::new(-12, 2))]
+    #[case(Money::new(-121, 3), RoundingMode::Floor, Money::new(-13, 2))]
+    fn should_round_with_mode(
+        #[case] money: Money,
+        #[case] mode: RoundingMode,
+        #[case] expected: Money,
+    ) {
+        let actual = money.round(2, mode).unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(actual.scale(), 2);
+    }
+
+    #[test]
+    fn should_add_and_sub_across_scales() {
+        let a = Money::new(1999, 2);
+        let b = Money::new(1, 3);
+
+        assert_eq!(a.checked_add(b).unwrap(), Money::new(19991, 3));
+        assert_eq!(a.checked_sub(b).unwrap(), Money::new(19989, 3));
+    }
+
+    #[test]
+    fn should_return_none_on_overflow() {
+        let max = Money::from_major(i64::MAX);
+
+        assert_eq!(max.checked_add(Money::from_major(1)), None);
+        assert_eq!(max.checked_mul(Money::from_major(2)), None);
+    }
+
+    #[test]
+    fn should_multiply_exactly() {
+        let actual = Money::new(1999, 2).checked_mul(Money::new(117, 2)).unwrap();
+        assert_eq!(actual, Money::new(233883, 4));
+    }
+
+    #[rstest]
+    #[case(
+        Money::from_major(1),
+        Money::from_major(3),
+        RoundingMode::HalfEven,
+        Money::new(3333, 4)
+    )]
+    #[case(
+        Money::from_major(2),
+        Money::from_major(3),
+        RoundingMode::HalfEven,
+        Money::new(6667, 4)
+    )]
+    #[case(
+        Money::from_major(2),
+        Money::from_major(3),
+        RoundingMode::Down,
+        Money::new(6666, 4)
+    )]
+    #[case(Money::new(-1, 0), Money::from_major(8), RoundingMode::HalfEven, Money::new(-1250, 4))]
+    #[case(Money::new(1, 3), Money::new(-4, 0), RoundingMode::Floor, Money::new(-3, 4))]
+    fn should_divide_with_rounding(
+        #[case] lhs: Money,
+        #[case] rhs: Money,
+        #[case] mode: RoundingMode,
+        #[case] expected: Money,
+    ) {
+        let actual = lhs.checked_div(rhs, 4, mode).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_return_none_on_division_by_zero() {
+        assert_eq!(
+            Money::from_major(1).checked_div(Money::ZERO, 2, RoundingMode::HalfEven),
+            None
+        );
+    }
+
+    #[test]
+    fn should_return_none_for_scale_above_max() {
+        let actual = Money::new(1999, 2).checked_mul_rounded(
+            Money::new(117, 2),
+            MAX_SCALE + 1,
+            RoundingMode::HalfEven,
+        );
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn should_reject_scale_above_max_when_constructing() {
+        assert_eq!(Money::try_new(1, MAX_SCALE), Some(Money::new(1, MAX_SCALE)));
+        assert_eq!(Money::try_new(1, MAX_SCALE + 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "scale must not exceed 18")]
+    fn should_panic_on_scale_above_max() {
+        Money::new(1, MAX_SCALE + 1);
+    }
+
+    #[test]
+    fn should_return_minor_units_for_currency() {
+        let actual = Money::new(19995, 3).minor_units(EUR, RoundingMode::HalfEven);
+        assert_eq!(actual, Some(2000));
+    }
+
+    #[test]
+    fn should_compare_numerically() {
+        assert!(Money::new(1999, 2) < Money::from_major(20));
+        assert_eq!(Money::new(4200, 2), Money::from_major(42));
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Attribute of an item-event: left as is, set to a value, or cleared.
+///
+/// Serializes compatibly with plain `Option` attributes when combined with
+/// `#[serde(default, skip_serializing_if = "Patch::is_unchanged")]`: an absent
+/// attribute is `Unchanged`, a value is `Set` and `null` is `Cleared`.
+#[derive(Clone, Default, Eq, PartialEq, Debug, Hash)]
+pub enum Patch<T> {
+    #[default]
+    Unchanged,
+    Set(T),
+    Cleared,
+}
+
+impl<T> Patch<T> {
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Patch::Unchanged)
+    }
+
+    pub fn is_set(&self) -> bool {
+        matches!(self, Patch::Set(_))
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        matches!(self, Patch::Cleared)
+    }
+
+    pub fn as_ref(&self) -> Patch<&T> {
+        match self {
+            Patch::Unchanged => Patch::Unchanged,
+            Patch::Set(value) => Patch::Set(value),
+            Patch::Cleared => Patch::Cleared,
+        }
+    }
+
+    /// The value, if set.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Patch::Set(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Patch::Set(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn take(&mut self) -> Patch<T> {
+        std::mem::take(self)
+    }
+
+    /// This patch, or `older` if this one leaves the attribute unchanged.
+    pub fn or(self, older: Patch<T>) -> Patch<T> {
+        match self {
+            Patch::Unchanged => older,
+            newer => newer,
+        }
+    }
+
+    /// State of the attribute after applying the patch to nothing: a cleared
+    /// attribute is simply absent.
+    pub fn resolved(self) -> Patch<T> {
+        match self {
+            Patch::Cleared => Patch::Unchanged,
+            patch => patch,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    /// `Some` sets the value, `None` leaves the attribute unchanged.
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Patch::Unchanged, Patch::Set)
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Patch::Set(value) => value.serialize(serializer),
+            Patch::Unchanged | Patch::Cleared => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(|value| value.map_or(Patch::Cleared, Patch::Set))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patch::Patch;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Event {
+        #[serde(default, skip_serializing_if = "Patch::is_unchanged")]
+        url: Patch<String>,
+    }
+
+    #[test]
+    fn should_encode_three_states() {
+        let unchanged = Event {
+            url: Patch::Unchanged,
+        };
+        let set = Event {
+            url: Patch::Set("https://foo.bar".to_string()),
+        };
+        let cleared = Event {
+            url: Patch::Cleared,
+        };
+
+        assert_eq!(serde_json::to_string(&unchanged).unwrap(), r#"{}"#);
+        assert_eq!(
+            serde_json::to_string(&set).unwrap(),
+            r#"{"url":"https://foo.bar"}"#
+        );
+        assert_eq!(serde_json::to_string(&cleared).unwrap(), r#"{"url":null}"#);
+    }
+
+    #[test]
+    fn should_decode_three_states() {
+        assert_eq!(
+            serde_json::from_str::<Event>(r#"{}"#).unwrap().url,
+            Patch::Unchanged
+        );
+        assert_eq!(
+            serde_json::from_str::<Event>(r#"{"url":"https://foo.bar"}"#)
+                .unwrap()
+                .url,
+            Patch::Set("https://foo.bar".to_string())
+        );
+        assert_eq!(
+            serde_json::from_str::<Event>(r#"{"url":null}"#)
+                .unwrap()
+                .url,
+            Patch::Cleared
+        );
+    }
+
+    #[test]
+    fn should_let_newer_patch_take_precedence() {
+        let set = Patch::Set(1);
+
+        assert_eq!(Patch::Unchanged.or(set.clone()), set);
+        assert_eq!(Patch::Cleared.or(set.clone()), Patch::Cleared);
+        assert_eq!(Patch::Set(2).or(set), Patch::Set(2));
+        assert_eq!(Patch::<i32>::Cleared.resolved(), Patch::Unchanged);
+    }
+}
@@ -1,52 +1,68 @@
+pub use crate::currency::Currency;
+use crate::exchange_rate::{DEFAULT_EXCHANGE_RATES, ExchangeRateProvider};
+use crate::money::{DEFAULT_ROUNDING_MODE, Money, RoundingMode};
 use serde::{Deserialize, Serialize};
-use strum_macros::{Display, EnumString};
-
-// ISO 4217
-#[derive(Serialize, Deserialize, Copy, Clone, Display, EnumString, Eq, PartialEq, Debug)]
-pub enum Currency {
-    EUR,
-    GBP,
-    USD,
-    AUD,
-    CAD,
-    NZD,
-}
-
-pub type EuroConversionRate = fn(Currency) -> f32;
+use time::OffsetDateTime;
 
-pub const DEFAULT_CONVERSION_RATE: EuroConversionRate = |currency| match currency {
-    Currency::EUR => 1.0,
-    Currency::GBP => 1.17,
-    Currency::USD => 0.9,
-    Currency::AUD => 0.58,
-    Currency::CAD => 0.67,
-    Currency::NZD => 0.53,
-};
-
-#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct Price {
     pub currency: Currency,
-    pub amount: f32,
+    pub amount: Money,
 }
 
 impl Price {
-    pub fn new(currency: Currency, amount: f32) -> Self {
+    pub fn new(currency: Currency, amount: Money) -> Self {
         Self { currency, amount }
     }
 
-    pub fn def_amount_in_euros(&self) -> f32 {
-        DEFAULT_CONVERSION_RATE(self.currency) * self.amount
+    pub fn from_minor_units(currency: Currency, minor_units: i64) -> Self {
+        Self::new(currency, Money::from_minor_units(minor_units, currency))
+    }
+
+    /// Amount in euros using [`DEFAULT_EXCHANGE_RATES`] and [`DEFAULT_ROUNDING_MODE`],
+    /// rounded to cents. `None` if the currency has no default rate (only EUR, GBP,
+    /// USD, AUD, CAD and NZD do) or the conversion overflows.
+    pub fn def_amount_in_euros(&self) -> Option<Money> {
+        self.amount_in_euros(
+            &DEFAULT_EXCHANGE_RATES,
+            OffsetDateTime::now_utc(),
+            DEFAULT_ROUNDING_MODE,
+        )
+    }
+
+    pub fn amount_in_euros(
+        &self,
+        rates: &dyn ExchangeRateProvider,
+        at: OffsetDateTime,
+        rounding_mode: RoundingMode,
+    ) -> Option<Money> {
+        self.amount_in(Currency::EUR, rates, at, rounding_mode)
     }
 
-    pub fn amount_in_euros(&self, conversion_rate: EuroConversionRate) -> f32 {
-        conversion_rate(self.currency) * self.amount
+    /// Amount converted to `currency` with the rate valid at `at`, rounded to its minor unit.
+    /// `None` if no rate is known or the conversion overflows.
+    pub fn amount_in(
+        &self,
+        currency: Currency,
+        rates: &dyn ExchangeRateProvider,
+        at: OffsetDateTime,
+        rounding_mode: RoundingMode,
+    ) -> Option<Money> {
+        self.amount.checked_mul_rounded(
+            rates.rate(self.currency, currency, at)?,
+            currency.minor_unit_exponent(),
+            rounding_mode,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::price::Currency;
+    use crate::exchange_rate::{DEFAULT_EXCHANGE_RATES, ExchangeRateTable};
+    use crate::money::{Money, RoundingMode};
+    use crate::price::{Currency, Price};
     use rstest::rstest;
+    use time::macros::datetime;
 
     #[rstest]
     #[case(Currency::EUR, "\"EUR\"")]
@@ -74,7 +90,87 @@ mod tests {
         #[case] currency: &str,
         #[case] expected: Currency,
     ) {
-        let actual = serde_json::from_str::<Currency>(&currency).unwrap();
+        let actual = serde_json::from_str::<Currency>(currency).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(Price::from_minor_units(Currency::EUR, 1999), Money::new(1999, 2))]
+    #[case(Price::from_minor_units(Currency::GBP, 1999), Money::new(2339, 2))]
+    #[case(Price::from_minor_units(Currency::USD, 1001), Money::new(901, 2))]
+    fn should_convert_amount_to_euro_cents(#[case] price: Price, #[case] expected: Money) {
+        let actual = price.def_amount_in_euros().unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(actual.scale(), 2);
+    }
+
+    #[test]
+    fn should_apply_rounding_mode_when_converting() {
+        let price = Price::from_minor_units(Currency::USD, 1001);
+
+        let actual = price
+            .amount_in_euros(
+                &DEFAULT_EXCHANGE_RATES,
+                datetime!(2024-01-05 12:00 UTC),
+                RoundingMode::Down,
+            )
+            .unwrap();
+
+        assert_eq!(actual, Money::new(900, 2));
+    }
+
+    #[test]
+    fn should_convert_with_rate_valid_at_instant() {
+        let mut rates = ExchangeRateTable::new(Currency::EUR);
+        rates
+            .insert(
+                Currency::USD,
+                datetime!(2024-01-04 00:00 UTC),
+                Money::new(125, 2),
+            )
+            .insert(
+                Currency::USD,
+                datetime!(2024-01-05 00:00 UTC),
+                Money::new(2, 0),
+            );
+        let price = Price::from_minor_units(Currency::USD, 1000);
+
+        let before = price.amount_in_euros(
+            &rates,
+            datetime!(2024-01-04 12:00 UTC),
+            RoundingMode::HalfEven,
+        );
+        let after = price.amount_in_euros(
+            &rates,
+            datetime!(2024-01-05 12:00 UTC),
+            RoundingMode::HalfEven,
+        );
+
+        assert_eq!(before, Some(Money::new(800, 2)));
+        assert_eq!(after, Some(Money::new(500, 2)));
+    }
+
+    #[test]
+    fn should_not_convert_without_known_rate() {
+        let rates = ExchangeRateTable::new(Currency::EUR);
+        let price = Price::from_minor_units(Currency::USD, 1000);
+
+        let actual = price.amount_in_euros(
+            &rates,
+            datetime!(2024-01-05 12:00 UTC),
+            RoundingMode::HalfEven,
+        );
+
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn should_deserialize_legacy_float_price() {
+        let json = r#"{"currency":"EUR","amount":19.99}"#;
+        let expected = Price::from_minor_units(Currency::EUR, 1999);
+
+        let actual = serde_json::from_str::<Price>(json).unwrap();
+
         assert_eq!(actual, expected);
     }
 }
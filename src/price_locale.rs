@@ -0,0 +1,437 @@
+use crate::language::Language;
+use crate::money::{DEFAULT_ROUNDING_MODE, Money};
+use crate::price::{Currency, Price};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SymbolPosition {
+    /// `€1,234.56`
+    Prefix,
+    /// `1.234,56 €`
+    Suffix,
+}
+
+/// Separators and currency placement a language uses when writing prices.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct NumberConventions {
+    pub decimal_separator: char,
+    pub grouping_separator: char,
+    pub symbol_position: SymbolPosition,
+}
+
+impl NumberConventions {
+    pub fn for_language(language: Language) -> Self {
+        match language {
+            Language::EN => NumberConventions {
+                decimal_separator: '.',
+                grouping_separator: ',',
+                symbol_position: SymbolPosition::Prefix,
+            },
+            Language::DE | Language::ES => NumberConventions {
+                decimal_separator: ',',
+                grouping_separator: '.',
+                symbol_position: SymbolPosition::Suffix,
+            },
+            Language::FR => NumberConventions {
+                decimal_separator: ',',
+                grouping_separator: '\u{202F}',
+                symbol_position: SymbolPosition::Suffix,
+            },
+        }
+    }
+
+    /// Writes a non-negative `amount` with all its fractional digits.
+    fn format_number(&self, amount: Money) -> String {
+        let plain = amount.to_string();
+        let (integer, fraction) = plain.split_once('.').unwrap_or((&plain, ""));
+        let mut formatted = String::with_capacity(plain.len() + integer.len() / 3);
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                formatted.push(self.grouping_separator);
+            }
+            formatted.push(digit);
+        }
+        if !fraction.is_empty() {
+            formatted.push(self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+
+    fn attach_symbol(&self, number: &str, currency: Currency, negative: bool) -> String {
+        let sign = if negative { "-" } else { "" };
+        let symbol = currency.symbol();
+        match self.symbol_position {
+            SymbolPosition::Suffix => format!("{sign}{number} {symbol}"),
+            // `CHF 12.00`, but `€12.00`
+            SymbolPosition::Prefix if symbol.ends_with(char::is_alphabetic) => {
+                format!("{sign}{symbol} {number}")
+            }
+            SymbolPosition::Prefix => format!("{sign}{symbol}{number}"),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParsePriceError {
+    Empty,
+    MissingCurrency,
+    UnknownCurrency(String),
+    /// A symbol like `kr` that several currencies share.
+    AmbiguousCurrency(String),
+    InvalidNumber(String),
+    /// A single `.` or `,` followed by exactly three digits, which is either a
+    /// decimal or a thousands separator depending on the locale.
+    AmbiguousSeparator {
+        number: String,
+        separator: char,
+    },
+}
+
+impl fmt::Display for ParsePriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePriceError::Empty => f.write_str("cannot parse price from empty string"),
+            ParsePriceError::MissingCurrency => f.write_str("price has no currency"),
+            ParsePriceError::UnknownCurrency(currency) => {
+                write!(f, "unknown currency '{currency}'")
+            }
+            ParsePriceError::AmbiguousCurrency(symbol) => {
+                write!(
+                    f,
+                    "currency symbol '{symbol}' is shared by several currencies"
+                )
+            }
+            ParsePriceError::InvalidNumber(number) => write!(f, "invalid amount '{number}'"),
+            ParsePriceError::AmbiguousSeparator { number, separator } => write!(
+                f,
+                "cannot tell whether '{separator}' in '{number}' is a decimal or thousands separator without a language hint"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParsePriceError {}
+
+/// Currency markers that are neither an alpha code nor a [`Currency::symbol`].
+const CURRENCY_ALIASES: &[(&str, Currency)] = &[
+    ("Fr.", Currency::CHF),
+    ("SFr.", Currency::CHF),
+    ("US$", Currency::USD),
+    ("Euro", Currency::EUR),
+    ("EURO", Currency::EUR),
+];
+
+const DASHES: &[char] = &['-', '–', '—'];
+
+fn is_grouping_only(c: char) -> bool {
+    matches!(c, '\'' | '’' | ' ' | '\u{A0}' | '\u{202F}')
+}
+
+fn parse_currency(marker: &str) -> Result<Currency, ParsePriceError> {
+    if let Ok(currency) = Currency::from_str(&marker.to_uppercase()) {
+        return Ok(currency);
+    }
+    if let Some((_, currency)) = CURRENCY_ALIASES.iter().find(|(alias, _)| *alias == marker) {
+        return Ok(*currency);
+    }
+    if let Some(currency) = Currency::from_symbol(marker) {
+        return Ok(currency);
+    }
+    if Currency::all_by_symbol(marker).next().is_some() {
+        Err(ParsePriceError::AmbiguousCurrency(marker.to_string()))
+    } else {
+        Err(ParsePriceError::UnknownCurrency(marker.to_string()))
+    }
+}
+
+/// Splits `input` into the currency marker and the number, e.g. `"1.234,56 €"`
+/// into `"€"` and `"1.234,56"`. Trailing dash notation (`12,-`) stays with the number.
+fn split_marker(input: &str) -> Result<(Option<&str>, &str), ParsePriceError> {
+    let start = input
+        .find(|c: char| c.is_ascii_digit())
+        .ok_or_else(|| ParsePriceError::InvalidNumber(input.to_string()))?;
+    let mut end = input
+        .rfind(|c: char| c.is_ascii_digit())
+        .map(|i| i + 1)
+        .unwrap_or(start);
+    let rest = &input[end..];
+    if let Some(after_separator) = rest.strip_prefix([',', '.']) {
+        let dashes = after_separator.len() - after_separator.trim_start_matches(DASHES).len();
+        if dashes > 0 {
+            end += 1 + dashes;
+        }
+    }
+    let prefix = input[..start].trim();
+    let suffix = input[end..].trim();
+    let marker = match (prefix.is_empty(), suffix.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(prefix),
+        (true, false) => Some(suffix),
+        (false, false) => return Err(ParsePriceError::InvalidNumber(input.to_string())),
+    };
+    Ok((marker, &input[start..end]))
+}
+
+/// Parses a number that may use `.`/`,` as decimal or thousands separator,
+/// apostrophes or spaces as thousands separator and trailing dash notation.
+fn parse_number(number: &str, hint: Option<Language>) -> Result<Money, ParsePriceError> {
+    let invalid = || ParsePriceError::InvalidNumber(number.to_string());
+    let body = number.trim_end_matches(DASHES);
+    // `12,-` marks whole units, which also settles which separator is the decimal one
+    let dash_separator = if body.len() != number.len() {
+        let separator = body.chars().last().filter(|c| matches!(c, ',' | '.'));
+        Some(separator.ok_or_else(invalid)?)
+    } else {
+        None
+    };
+    let body = match dash_separator {
+        Some(_) => &body[..body.len() - 1],
+        None => body,
+    };
+
+    let dots = body.matches('.').count();
+    let commas = body.matches(',').count();
+    let decimal_separator = match (dash_separator, dots, commas) {
+        (Some(separator), _, _) if body.contains(separator) => return Err(invalid()),
+        (Some(_), _, _) | (None, 0, 0) => None,
+        (None, _, 0) | (None, 0, _) => {
+            let separator = if dots > 0 { '.' } else { ',' };
+            let fraction_digits = body.len() - body.rfind(separator).unwrap_or_default() - 1;
+            if dots + commas > 1 {
+                None
+            } else if fraction_digits != 3 {
+                Some(separator)
+            } else {
+                match hint {
+                    Some(language) => Some(separator).filter(|s| {
+                        *s == NumberConventions::for_language(language).decimal_separator
+                    }),
+                    None => {
+                        return Err(ParsePriceError::AmbiguousSeparator {
+                            number: number.to_string(),
+                            separator,
+                        });
+                    }
+                }
+            }
+        }
+        (None, _, _) => {
+            let last_dot = body.rfind('.').unwrap_or_default();
+            let last_comma = body.rfind(',').unwrap_or_default();
+            Some(if last_dot > last_comma { '.' } else { ',' })
+        }
+    };
+
+    let (integer, fraction) = match decimal_separator {
+        Some(separator) => body.rsplit_once(separator).ok_or_else(invalid)?,
+        None => (body, ""),
+    };
+    if decimal_separator.is_some_and(|separator| integer.contains(separator)) {
+        return Err(invalid());
+    }
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    // thousands groups after the first must have exactly three digits
+    let groups: Vec<&str> = integer
+        .split(|c: char| c == '.' || c == ',' || is_grouping_only(c))
+        .collect();
+    let well_formed = groups
+        .iter()
+        .all(|g| !g.is_empty() && g.bytes().all(|b| b.is_ascii_digit()))
+        && groups.iter().skip(1).all(|g| g.len() == 3);
+    if !well_formed {
+        return Err(invalid());
+    }
+
+    format!("{}.{}", groups.concat(), fraction)
+        .parse()
+        .map_err(|_| invalid())
+}
+
+impl Price {
+    /// Parses a scraped price such as `"1.234,56 €"`, `"£1,234.56"`, `"EUR 12,-"` or
+    /// `"CHF 1'200.00"`. The currency may precede or follow the amount, as alpha code or
+    /// symbol. `hint` decides whether a lone `.` or `,` followed by three digits separates
+    /// decimals or thousands; without it such input is rejected as ambiguous.
+    pub fn parse_localized(input: &str, hint: Option<Language>) -> Result<Price, ParsePriceError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(ParsePriceError::Empty);
+        }
+        let (marker, number) = split_marker(input)?;
+        let amount = parse_number(number, hint)?;
+        let currency = parse_currency(marker.ok_or(ParsePriceError::MissingCurrency)?)?;
+        Ok(Price::new(currency, amount))
+    }
+}
+
+/// Suffixes for the compact form, by power of thousand.
+const COMPACT_SUFFIXES: &[(u32, &str)] = &[(9, "B"), (6, "M"), (3, "k")];
+
+impl Price {
+    /// Formats for display in `language`, e.g. `1.234,56 €` for DE and `€1,234.56` for EN.
+    /// The amount is rounded to the minor unit of the currency.
+    pub fn format_localized(&self, language: Language) -> String {
+        let conventions = NumberConventions::for_language(language);
+        let amount = self
+            .amount
+            .round_to(self.currency, DEFAULT_ROUNDING_MODE)
+            .unwrap_or(self.amount);
+        let abs = amount
+            .checked_neg()
+            .filter(|_| amount.is_negative())
+            .unwrap_or(amount);
+        let number = conventions.format_number(abs);
+        conventions.attach_symbol(&number, self.currency, amount.is_negative())
+    }
+
+    /// Short form with at most one fractional digit and a magnitude suffix,
+    /// e.g. `1,2k €` for DE and `€3.4M` for EN. Amounts below a thousand
+    /// are formatted like [`Price::format_localized`].
+    pub fn format_compact(&self, language: Language) -> String {
+        let conventions = NumberConventions::for_language(language);
+        let negative = self.amount.is_negative();
+        let abs = self
+            .amount
+            .checked_neg()
+            .filter(|_| negative)
+            .unwrap_or(self.amount);
+        let thousand = Money::from_major(1000);
+        for (index, (exponent, suffix)) in COMPACT_SUFFIXES.iter().enumerate() {
+            let divisor = Money::from_major(10i64.pow(*exponent));
+            if abs < divisor {
+                continue;
+            }
+            let Some(mut scaled) = abs.checked_div(divisor, 1, DEFAULT_ROUNDING_MODE) else {
+                continue;
+            };
+            let mut suffix = *suffix;
+            // 999.95k rounds up to 1000k, which reads better as 1M
+            if scaled >= thousand && index > 0 {
+                scaled = Money::from_major(1);
+                suffix = COMPACT_SUFFIXES[index - 1].1;
+            }
+            let number = conventions.format_number(scaled.normalized());
+            return conventions.attach_symbol(
+                &format!("{number}{suffix}"),
+                self.currency,
+                negative,
+            );
+        }
+        self.format_localized(language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::Language;
+    use crate::money::Money;
+    use crate::price::Currency::{CHF, EUR, GBP, JPY, USD};
+    use crate::price::{Currency, Price};
+    use crate::price_locale::ParsePriceError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Price::new(EUR, Money::new(123456, 2)), Language::DE, "1.234,56 €")]
+    #[case(Price::new(EUR, Money::new(123456, 2)), Language::EN, "€1,234.56")]
+    #[case(Price::new(EUR, Money::new(123456, 2)), Language::ES, "1.234,56 €")]
+    #[case(
+        Price::new(EUR, Money::new(123456, 2)),
+        Language::FR,
+        "1\u{202F}234,56 €"
+    )]
+    #[case(Price::new(EUR, Money::from_major(42)), Language::DE, "42,00 €")]
+    #[case(Price::new(EUR, Money::new(19999, 3)), Language::DE, "20,00 €")]
+    #[case(Price::new(EUR, Money::new(-5, 1)), Language::EN, "-€0.50")]
+    #[case(
+        Price::new(JPY, Money::from_major(1234567)),
+        Language::EN,
+        "¥1,234,567"
+    )]
+    #[case(Price::new(CHF, Money::from_major(1200)), Language::EN, "CHF 1,200.00")]
+    #[case(Price::new(CHF, Money::from_major(1200)), Language::DE, "1.200,00 CHF")]
+    fn should_format_localized_price(
+        #[case] price: Price,
+        #[case] language: Language,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(price.format_localized(language), expected);
+    }
+
+    #[rstest]
+    #[case(Price::new(EUR, Money::new(123456, 2)), Language::DE, "1,2k €")]
+    #[case(Price::new(EUR, Money::new(123456, 2)), Language::EN, "€1.2k")]
+    #[case(Price::new(EUR, Money::from_major(1000)), Language::DE, "1k €")]
+    #[case(Price::new(EUR, Money::from_major(3_450_000)), Language::EN, "€3.4M")]
+    #[case(Price::new(USD, Money::from_major(2_000_000_000)), Language::EN, "$2B")]
+    #[case(Price::new(EUR, Money::new(-250000, 2)), Language::DE, "-2,5k €")]
+    #[case(Price::new(EUR, Money::new(99999, 2)), Language::DE, "999,99 €")]
+    #[case(Price::new(EUR, Money::from_major(999_950)), Language::DE, "1M €")]
+    fn should_format_compact_price(
+        #[case] price: Price,
+        #[case] language: Language,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(price.format_compact(language), expected);
+    }
+
+    #[rstest]
+    #[case(Price::new(EUR, Money::new(123456, 2)))]
+    #[case(Price::new(GBP, Money::new(99, 2)))]
+    #[case(Price::new(JPY, Money::from_major(1234567)))]
+    fn should_parse_what_was_formatted(#[case] price: Price) {
+        for language in [Language::DE, Language::EN, Language::FR, Language::ES] {
+            let formatted = price.format_localized(language);
+            let actual = Price::parse_localized(&formatted, Some(language)).unwrap();
+            assert_eq!(actual, price);
+        }
+    }
+
+    #[rstest]
+    #[case("1.234,56 €", None, EUR, Money::new(123456, 2))]
+    #[case("£1,234.56", None, GBP, Money::new(123456, 2))]
+    #[case("EUR 12,-", None, EUR, Money::from_major(12))]
+    #[case("12.– €", None, EUR, Money::from_major(12))]
+    #[case("1.234,- €", None, EUR, Money::from_major(1234))]
+    #[case("CHF 1'200.00", None, CHF, Money::from_major(1200))]
+    #[case("1 234,5 EUR", None, EUR, Money::new(12345, 1))]
+    #[case("1\u{202F}234,56\u{A0}€", None, EUR, Money::new(123456, 2))]
+    #[case("$19.99", None, USD, Money::new(1999, 2))]
+    #[case("¥1,234,567", None, JPY, Money::from_major(1234567))]
+    #[case("42 eur", None, EUR, Money::from_major(42))]
+    #[case("Fr. 9.50", None, CHF, Money::new(95, 1))]
+    #[case("1.234 €", Some(Language::DE), EUR, Money::from_major(1234))]
+    #[case("1.234 €", Some(Language::EN), EUR, Money::new(1234, 3))]
+    #[case("1,234 €", Some(Language::EN), EUR, Money::from_major(1234))]
+    #[case("1,234 €", Some(Language::FR), EUR, Money::new(1234, 3))]
+    fn should_parse_localized_price(
+        #[case] input: &str,
+        #[case] hint: Option<Language>,
+        #[case] currency: Currency,
+        #[case] amount: Money,
+    ) {
+        let actual = Price::parse_localized(input, hint).unwrap();
+        assert_eq!(actual, Price::new(currency, amount));
+    }
+
+    #[rstest]
+    #[case("", ParsePriceError::Empty)]
+    #[case("1.234", ParsePriceError::AmbiguousSeparator { number: "1.234".to_string(), separator: '.' })]
+    #[case("1.234,56", ParsePriceError::MissingCurrency)]
+    #[case("1,234,- €", ParsePriceError::InvalidNumber("1,234,-".to_string()))]
+    #[case("1.234 €", ParsePriceError::AmbiguousSeparator { number: "1.234".to_string(), separator: '.' })]
+    #[case("12 kr", ParsePriceError::AmbiguousCurrency("kr".to_string()))]
+    #[case("12 Taler", ParsePriceError::UnknownCurrency("Taler".to_string()))]
+    #[case("€ 1.2.3", ParsePriceError::InvalidNumber("1.2.3".to_string()))]
+    #[case("€ 1,23.45", ParsePriceError::InvalidNumber("1,23.45".to_string()))]
+    #[case("€ 1.234.56", ParsePriceError::InvalidNumber("1.234.56".to_string()))]
+    #[case("€", ParsePriceError::InvalidNumber("€".to_string()))]
+    fn should_reject_invalid_price(#[case] input: &str, #[case] expected: ParsePriceError) {
+        let actual = Price::parse_localized(input, None).unwrap_err();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,337 @@
+use crate::exchange_rate::ExchangeRateProvider;
+use crate::item_model::ItemModel;
+use crate::money::{DEFAULT_ROUNDING_MODE, Money, RoundingMode};
+use crate::price::{Currency, Price};
+use serde::{Deserialize, Serialize};
+use std::ops::Bound;
+use time::OffsetDateTime;
+
+/// Price interval whose bounds may be open, closed or absent, each in any currency.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct PriceRange {
+    pub lower: Bound<Price>,
+    pub upper: Bound<Price>,
+}
+
+impl PriceRange {
+    pub fn new(lower: Bound<Price>, upper: Bound<Price>) -> Self {
+        PriceRange { lower, upper }
+    }
+
+    pub fn unbounded() -> Self {
+        PriceRange::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    /// `min <= price <= max`
+    pub fn between(min: Price, max: Price) -> Self {
+        PriceRange::new(Bound::Included(min), Bound::Included(max))
+    }
+
+    pub fn at_least(min: Price) -> Self {
+        PriceRange::new(Bound::Included(min), Bound::Unbounded)
+    }
+
+    pub fn at_most(max: Price) -> Self {
+        PriceRange::new(Bound::Unbounded, Bound::Included(max))
+    }
+
+    pub fn above(min: Price) -> Self {
+        PriceRange::new(Bound::Excluded(min), Bound::Unbounded)
+    }
+
+    pub fn below(max: Price) -> Self {
+        PriceRange::new(Bound::Unbounded, Bound::Excluded(max))
+    }
+
+    /// Whether `price` lies within the range at the current exchange rates.
+    pub fn contains(&self, price: &Price, rates: &dyn ExchangeRateProvider) -> bool {
+        self.contains_at(price, rates, OffsetDateTime::now_utc())
+    }
+
+    /// Whether `price` lies within the range at the rates valid at `at`. Bounds in another
+    /// currency are compared exactly after conversion; if a rate is missing the price is
+    /// not considered contained.
+    pub fn contains_at(
+        &self,
+        price: &Price,
+        rates: &dyn ExchangeRateProvider,
+        at: OffsetDateTime,
+    ) -> bool {
+        let above_lower = match &self.lower {
+            Bound::Unbounded => true,
+            Bound::Included(bound) => convert(price, bound.currency, rates, at)
+                .is_some_and(|amount| amount >= bound.amount),
+            Bound::Excluded(bound) => convert(price, bound.currency, rates, at)
+                .is_some_and(|amount| amount > bound.amount),
+        };
+        let below_upper = match &self.upper {
+            Bound::Unbounded => true,
+            Bound::Included(bound) => convert(price, bound.currency, rates, at)
+                .is_some_and(|amount| amount <= bound.amount),
+            Bound::Excluded(bound) => convert(price, bound.currency, rates, at)
+                .is_some_and(|amount| amount < bound.amount),
+        };
+        above_lower && below_upper
+    }
+}
+
+fn convert(
+    price: &Price,
+    currency: Currency,
+    rates: &dyn ExchangeRateProvider,
+    at: OffsetDateTime,
+) -> Option<Money> {
+    if price.currency == currency {
+        return Some(price.amount);
+    }
+    price
+        .amount
+        .checked_mul(rates.rate(price.currency, currency, at)?)
+}
+
+/// Scheme that maps non-negative EUR amounts, such as `ItemModel::price`, to stable,
+/// lexicographically sortable bucket ids for indexing and faceting.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub enum PriceBucketing {
+    /// Bucket `n` covers `[n * width, (n + 1) * width)`.
+    Linear { width: Money },
+    /// Bucket `0` covers `[0, start)`; bucket `n` covers
+    /// `[start * 10^((n - 1) / per_decade), start * 10^(n / per_decade))`,
+    /// with boundaries rounded to cents.
+    Logarithmic { start: Money, per_decade: u32 },
+}
+
+impl PriceBucketing {
+    /// Bucket index of a non-negative EUR amount; `None` for negative amounts
+    /// or a degenerate scheme.
+    pub fn bucket(&self, eur: Money) -> Option<u32> {
+        if eur.is_negative() {
+            return None;
+        }
+        match self {
+            PriceBucketing::Linear { width } => {
+                if width.is_negative() || width.is_zero() {
+                    return None;
+                }
+                let index = eur.checked_div(*width, 0, RoundingMode::Floor)?;
+                u32::try_from(index.mantissa()).ok()
+            }
+            PriceBucketing::Logarithmic { start, per_decade } => {
+                if start.is_negative() || start.is_zero() || *per_decade == 0 {
+                    return None;
+                }
+                if eur < *start {
+                    return Some(0);
+                }
+                // estimate with floats, then settle exactly against the rounded boundaries
+                let estimate = ((eur.to_f64() / start.to_f64()).log10() * *per_decade as f64)
+                    .floor()
+                    .max(0.0) as u32
+                    + 1;
+                let mut index = estimate;
+                while index > 1 && eur < self.lower_bound(index)? {
+                    index -= 1;
+                }
+                while eur >= self.lower_bound(index + 1)? {
+                    index += 1;
+                }
+                Some(index)
+            }
+        }
+    }
+
+    /// Inclusive lower boundary of bucket `index` in EUR.
+    pub fn lower_bound(&self, index: u32) -> Option<Money> {
+        match self {
+            PriceBucketing::Linear { width } => width.checked_mul(Money::from_major(index as i64)),
+            PriceBucketing::Logarithmic { start, per_decade } => {
+                if index == 0 {
+                    return Some(Money::ZERO);
+                }
+                let factor = 10f64.powf((index - 1) as f64 / *per_decade as f64);
+                Money::from_f64(start.to_f64() * factor)?
+                    .round_to(Currency::EUR, DEFAULT_ROUNDING_MODE)
+            }
+        }
+    }
+
+    /// Bucket `index` as a range of EUR prices.
+    pub fn range(&self, index: u32) -> Option<PriceRange> {
+        let lower = Price::new(Currency::EUR, self.lower_bound(index)?);
+        let upper = Price::new(Currency::EUR, self.lower_bound(index + 1)?);
+        Some(PriceRange::new(
+            Bound::Included(lower),
+            Bound::Excluded(upper),
+        ))
+    }
+
+    /// Short name of the scheme and its parameters, e.g. `lin50` or `log1x4`,
+    /// so ids from differently configured schemes never collide.
+    pub fn key(&self) -> String {
+        match self {
+            PriceBucketing::Linear { width } => format!("lin{}", width.normalized()),
+            PriceBucketing::Logarithmic { start, per_decade } => {
+                format!("log{}x{per_decade}", start.normalized())
+            }
+        }
+    }
+
+    /// Indexable id such as `log1x4#0000000009`, zero-padded to the width of `u32`
+    /// so ids sort by price.
+    pub fn bucket_id(&self, eur: Money) -> Option<String> {
+        self.bucket(eur)
+            .map(|index| format!("{}#{index:010}", self.key()))
+    }
+
+    pub fn bucket_id_for(&self, item: &ItemModel) -> Option<String> {
+        self.bucket_id(item.price?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exchange_rate::{DEFAULT_EXCHANGE_RATES, ExchangeRateTable};
+    use crate::item_model::ItemModel;
+    use crate::money::Money;
+    use crate::price::Currency::{EUR, GBP, USD};
+    use crate::price::Price;
+    use crate::price_range::{PriceBucketing, PriceRange};
+    use rstest::rstest;
+    use std::ops::Bound;
+    use time::macros::datetime;
+
+    fn eur(amount: i64) -> Price {
+        Price::new(EUR, Money::from_major(amount))
+    }
+
+    #[rstest]
+    #[case(PriceRange::between(eur(10), eur(20)), eur(10), true)]
+    #[case(PriceRange::between(eur(10), eur(20)), eur(20), true)]
+    #[case(PriceRange::between(eur(10), eur(20)), eur(21), false)]
+    #[case(PriceRange::above(eur(10)), eur(10), false)]
+    #[case(PriceRange::below(eur(10)), eur(10), false)]
+    #[case(PriceRange::at_least(eur(10)), eur(1000), true)]
+    #[case(PriceRange::at_most(eur(10)), eur(9), true)]
+    #[case(PriceRange::unbounded(), eur(9), true)]
+    fn should_respect_open_and_closed_bounds(
+        #[case] range: PriceRange,
+        #[case] price: Price,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(range.contains(&price, &DEFAULT_EXCHANGE_RATES), expected);
+    }
+
+    #[test]
+    fn should_compare_prices_in_other_currencies() {
+        // 1 GBP = 1.17 EUR
+        let range = PriceRange::between(eur(100), eur(117));
+
+        assert!(range.contains(
+            &Price::new(GBP, Money::from_major(100)),
+            &DEFAULT_EXCHANGE_RATES
+        ));
+        assert!(!range.contains(
+            &Price::new(GBP, Money::new(10001, 2)),
+            &DEFAULT_EXCHANGE_RATES
+        ));
+    }
+
+    #[test]
+    fn should_compare_with_rate_valid_at_instant() {
+        let mut rates = ExchangeRateTable::new(EUR);
+        rates
+            .insert(USD, datetime!(2024-01-01 00:00 UTC), Money::from_major(1))
+            .insert(USD, datetime!(2024-02-01 00:00 UTC), Money::from_major(2));
+        let range = PriceRange::at_most(eur(10));
+        let price = Price::new(USD, Money::from_major(15));
+
+        assert!(!range.contains_at(&price, &rates, datetime!(2024-01-15 00:00 UTC)));
+        assert!(range.contains_at(&price, &rates, datetime!(2024-02-15 00:00 UTC)));
+    }
+
+    #[test]
+    fn should_not_contain_price_without_known_rate() {
+        let range = PriceRange::new(Bound::Included(eur(0)), Bound::Unbounded);
+        let rates = ExchangeRateTable::new(EUR);
+
+        assert!(!range.contains(&Price::new(USD, Money::from_major(15)), &rates));
+    }
+
+    #[rstest]
+    #[case(Money::ZERO, Some(0))]
+    #[case(Money::new(4999, 2), Some(0))]
+    #[case(Money::from_major(50), Some(1))]
+    #[case(Money::from_major(1234), Some(24))]
+    #[case(Money::from_major(-1), None)]
+    fn should_bucket_linearly(#[case] eur: Money, #[case] expected: Option<u32>) {
+        let bucketing = PriceBucketing::Linear {
+            width: Money::from_major(50),
+        };
+        assert_eq!(bucketing.bucket(eur), expected);
+    }
+
+    #[rstest]
+    #[case(Money::new(99, 2), 0)]
+    #[case(Money::from_major(1), 1)]
+    #[case(Money::new(177, 2), 1)]
+    #[case(Money::new(178, 2), 2)]
+    #[case(Money::new(999, 2), 4)]
+    #[case(Money::from_major(10), 5)]
+    #[case(Money::from_major(1000), 13)]
+    #[case(Money::new(99999, 2), 12)]
+    fn should_bucket_logarithmically(#[case] eur: Money, #[case] expected: u32) {
+        let bucketing = PriceBucketing::Logarithmic {
+            start: Money::from_major(1),
+            per_decade: 4,
+        };
+        assert_eq!(bucketing.bucket(eur), Some(expected));
+    }
+
+    #[test]
+    fn should_keep_every_amount_within_its_bucket_range() {
+        let bucketing = PriceBucketing::Logarithmic {
+            start: Money::from_major(1),
+            per_decade: 4,
+        };
+        for cents in (0..200_000).step_by(37) {
+            let amount = Money::new(cents, 2);
+            let index = bucketing.bucket(amount).unwrap();
+            let range = bucketing.range(index).unwrap();
+            assert!(range.contains(&Price::new(EUR, amount), &DEFAULT_EXCHANGE_RATES));
+        }
+    }
+
+    #[test]
+    fn should_build_sortable_bucket_id_for_item() {
+        let bucketing = PriceBucketing::Logarithmic {
+            start: Money::from_major(1),
+            per_decade: 4,
+        };
+        let item = ItemModel::new("foo#123456".to_string())
+            .price(Money::from_major(42))
+            .to_owned();
+
+        assert_eq!(
+            bucketing.bucket_id_for(&item),
+            Some("log1x4#0000000007".to_string())
+        );
+        assert_eq!(
+            bucketing.bucket_id_for(&ItemModel::new("foo#1".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn should_sort_bucket_ids_beyond_four_digits() {
+        let bucketing = PriceBucketing::Linear {
+            width: Money::new(1, 2),
+        };
+
+        let below = bucketing.bucket_id(Money::new(9999, 2)).unwrap();
+        let above = bucketing.bucket_id(Money::new(10000, 2)).unwrap();
+
+        assert_eq!(below, "lin0.01#0000009999");
+        assert_eq!(above, "lin0.01#0000010000");
+        assert!(below < above);
+    }
+}
@@ -0,0 +1,10 @@
+use crate::item_model::ItemModel;
+
+/// Item-event of item `foo#123456` created at `created`, e.g.
+/// `2010-01-01T11:00:00.001Z`, whose event id ends with it.
+pub fn event(created: &str) -> ItemModel {
+    ItemModel::new("foo#123456".to_string())
+        .created(created.parse().unwrap())
+        .event_id(format!("foo#123456#{created}"))
+        .to_owned()
+}
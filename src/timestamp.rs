@@ -0,0 +1,172 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use time::format_description::FormatItem;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{OffsetDateTime, UtcOffset};
+
+const FORMAT: &[FormatItem<'static>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z");
+
+/// Instant of an item-event, normalized to UTC with millisecond precision.
+///
+/// Formats as fixed-width RFC 3339, e.g. `2010-01-01T11:00:00.001Z`, so the
+/// lexicographic order of formatted timestamps - and of sort-keys built from
+/// them - equals their chronological order.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct Timestamp(OffsetDateTime);
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParseTimestampError {
+    Malformed(String),
+    OutOfRange(String),
+}
+
+impl fmt::Display for ParseTimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTimestampError::Malformed(input) => {
+                write!(f, "malformed RFC 3339 timestamp '{input}'")
+            }
+            ParseTimestampError::OutOfRange(input) => {
+                write!(f, "timestamp '{input}' is outside the years 0000 to 9999")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseTimestampError {}
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Timestamp::normalize(OffsetDateTime::now_utc())
+    }
+
+    pub fn to_offset_date_time(self) -> OffsetDateTime {
+        self.0
+    }
+
+    fn normalize(instant: OffsetDateTime) -> Self {
+        let utc = instant.to_offset(UtcOffset::UTC);
+        let millis = utc.nanosecond() / 1_000_000 * 1_000_000;
+        Timestamp(utc.replace_nanosecond(millis).unwrap_or(utc))
+    }
+}
+
+impl TryFrom<OffsetDateTime> for Timestamp {
+    type Error = ParseTimestampError;
+
+    /// Normalizes `instant` to UTC, truncating it to milliseconds.
+    fn try_from(instant: OffsetDateTime) -> Result<Self, Self::Error> {
+        let timestamp = Timestamp::normalize(instant);
+        if (0..=9999).contains(&timestamp.0.year()) {
+            Ok(timestamp)
+        } else {
+            Err(ParseTimestampError::OutOfRange(instant.to_string()))
+        }
+    }
+}
+
+impl From<Timestamp> for OffsetDateTime {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ParseTimestampError;
+
+    /// Parses any RFC 3339 timestamp, e.g. `2010-01-01T12:00:00.001+01:00`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let instant = OffsetDateTime::parse(s, &Rfc3339)
+            .map_err(|_| ParseTimestampError::Malformed(s.to_string()))?;
+        Timestamp::try_from(instant).map_err(|_| ParseTimestampError::OutOfRange(s.to_string()))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted = self.0.format(FORMAT).map_err(|_| fmt::Error)?;
+        f.write_str(&formatted)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timestamp::{ParseTimestampError, Timestamp};
+    use rstest::rstest;
+    use time::macros::datetime;
+
+    #[rstest]
+    #[case("2010-01-01T12:00:00.001+01:00", "2010-01-01T11:00:00.001Z")]
+    #[case("2010-01-01T12:00:00Z", "2010-01-01T12:00:00.000Z")]
+    #[case("2010-01-01T12:00:00.123456789Z", "2010-01-01T12:00:00.123Z")]
+    #[case("2010-01-01T00:30:00-01:00", "2010-01-01T01:30:00.000Z")]
+    fn should_normalize_to_utc_millis(#[case] input: &str, #[case] expected: &str) {
+        let actual = input.parse::<Timestamp>().unwrap();
+        assert_eq!(actual.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("abcdef")]
+    #[case("2010-01-01")]
+    #[case("2010-13-01T12:00:00Z")]
+    #[case("2010-01-01 12:00:00")]
+    fn should_reject_malformed_input(#[case] input: &str) {
+        let actual = input.parse::<Timestamp>();
+        assert_eq!(
+            actual,
+            Err(ParseTimestampError::Malformed(input.to_string()))
+        );
+    }
+
+    #[test]
+    fn should_order_lexicographically_as_chronologically() {
+        let mut timestamps = [
+            "2010-01-01T12:00:00.000+01:00",
+            "2010-01-01T11:30:00.000Z",
+            "2010-01-01T10:59:59.999Z",
+            "2009-12-31T23:00:00.000-12:00",
+        ]
+        .map(|s| s.parse::<Timestamp>().unwrap());
+        timestamps.sort();
+
+        let formatted = timestamps.map(|t| t.to_string());
+        let mut sorted = formatted.clone();
+        sorted.sort();
+
+        assert_eq!(formatted, sorted);
+    }
+
+    #[test]
+    fn should_round_trip_serde() {
+        let timestamp = Timestamp::try_from(datetime!(2010-01-01 12:00:00.001 +01:00)).unwrap();
+
+        let serialized = serde_json::to_string(&timestamp).unwrap();
+        let deserialized = serde_json::from_str::<Timestamp>(&serialized).unwrap();
+
+        assert_eq!(serialized, r#""2010-01-01T11:00:00.001Z""#);
+        assert_eq!(deserialized, timestamp);
+    }
+}